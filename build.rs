@@ -0,0 +1,62 @@
+fn main() {
+    #[cfg(feature = "link")]
+    link();
+
+    // The mock defines libdecor's functions in the test binary itself, where
+    // `dlsym(RTLD_DEFAULT, ...)` only finds them if the binary exports its symbols.
+    #[cfg(all(feature = "mock", feature = "weak"))]
+    println!("cargo:rustc-link-arg=-rdynamic");
+}
+
+#[cfg(feature = "link")]
+fn link() {
+    use std::{env, path::Path};
+
+    println!("cargo:rerun-if-env-changed=LIBDECOR_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=LIBDECOR_STATIC");
+    println!("cargo:rerun-if-env-changed=LIBDECOR_DBUS");
+
+    let statik = env::var_os("LIBDECOR_STATIC").is_some_and(|value| value != "0");
+
+    if let Some(lib_dir) = env::var_os("LIBDECOR_LIB_DIR") {
+        let kind = if statik { "static" } else { "dylib" };
+        println!(
+            "cargo:rustc-link-search=native={}",
+            Path::new(&lib_dir).display()
+        );
+        println!("cargo:rustc-link-lib={kind}=decor-0");
+        if statik {
+            // What pkg-config would resolve from libdecor-0.pc's private dependencies. libdecor
+            // uses dbus-1 to read the cursor settings unless it was built with `-Ddbus=disabled`.
+            let dbus = env::var_os("LIBDECOR_DBUS").is_none_or(|value| value != "0");
+            let private = ["wayland-client", "dl", "m"].into_iter();
+            for lib in private.chain(dbus.then_some("dbus-1")) {
+                println!("cargo:rustc-link-lib=dylib={lib}");
+            }
+        }
+        return;
+    }
+
+    #[cfg(feature = "libdecor-0-2-2")]
+    let min_version = "0.2.2";
+    #[cfg(all(feature = "libdecor-0-2-1", not(feature = "libdecor-0-2-2")))]
+    let min_version = "0.2.1";
+    #[cfg(all(feature = "libdecor-0-2-0", not(feature = "libdecor-0-2-1")))]
+    let min_version = "0.2.0";
+    #[cfg(all(feature = "libdecor-0-1-1", not(feature = "libdecor-0-2-0")))]
+    let min_version = "0.1.1";
+    #[cfg(not(feature = "libdecor-0-1-1"))]
+    let min_version = "0.1.0";
+
+    let result = pkg_config::Config::new()
+        .atleast_version(min_version)
+        .statik(statik)
+        .probe("libdecor-0");
+    if let Err(err) = result {
+        panic!(
+            "the `link` feature requires libdecor-0 >= {min_version}, which pkg-config couldn't \
+             find; install libdecor's development files or set LIBDECOR_LIB_DIR to the directory \
+             containing libdecor-0.so\n\n{err}"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Compile-time checks that the `#[repr(C)]` declarations match libdecor's C layout.
+
+use core::mem::{align_of, offset_of, size_of};
+
+use wayland_headers::wayland_util::wl_list;
+
+use crate::{libdecor::*, libdecor_plugin as plugin};
+
+#[cfg(test)]
+mod test;
+
+const PTR: usize = size_of::<*const ()>();
+
+macro_rules! assert_callbacks {
+    ($struct:ty { $($field:ident),* $(,)? }) => {
+        const _: () = {
+            let mut offset = 0;
+            $(
+                assert!(offset_of!($struct, $field) == offset);
+                offset += PTR;
+            )*
+            assert!(size_of::<$struct>() == offset);
+            assert!(align_of::<$struct>() == align_of::<*const ()>());
+        };
+    };
+}
+
+assert_callbacks!(libdecor_frame_interface {
+    configure,
+    close,
+    commit,
+    dismiss_popup,
+    reserved0,
+    reserved1,
+    reserved2,
+    reserved3,
+    reserved4,
+    reserved5,
+    reserved6,
+    reserved7,
+    reserved8,
+    reserved9,
+});
+
+assert_callbacks!(libdecor_interface {
+    error,
+    reserved0,
+    reserved1,
+    reserved2,
+    reserved3,
+    reserved4,
+    reserved5,
+    reserved6,
+    reserved7,
+    reserved8,
+    reserved9,
+});
+
+assert_callbacks!(plugin::libdecor_plugin_interface {
+    destroy,
+    get_fd,
+    dispatch,
+    set_handle_application_cursor,
+    frame_new,
+    frame_free,
+    frame_commit,
+    frame_property_changed,
+    frame_popup_grab,
+    frame_popup_ungrab,
+    frame_get_border_size,
+    reserved0,
+    reserved1,
+    reserved2,
+    reserved3,
+    reserved4,
+    reserved5,
+    reserved6,
+    reserved7,
+    reserved8,
+    reserved9,
+});
+
+const _: () = {
+    assert!(offset_of!(plugin::libdecor_frame, r#priv) == 0);
+    assert!(offset_of!(plugin::libdecor_frame, link) == PTR);
+    assert!(size_of::<plugin::libdecor_frame>() == PTR + size_of::<wl_list>());
+
+    assert!(size_of::<plugin::libdecor_plugin>() == PTR);
+
+    assert!(offset_of!(plugin::libdecor_plugin_priority, desktop) == 0);
+    assert!(offset_of!(plugin::libdecor_plugin_priority, priority) == PTR);
+    assert!(size_of::<plugin::libdecor_plugin_priority>() == 2 * PTR);
+
+    assert!(offset_of!(plugin::libdecor_plugin_description, api_version) == 0);
+    assert!(offset_of!(plugin::libdecor_plugin_description, description) == PTR);
+    assert!(offset_of!(plugin::libdecor_plugin_description, capabilities) == 2 * PTR);
+    assert!(offset_of!(plugin::libdecor_plugin_description, priorities) == 3 * PTR);
+    assert!(offset_of!(plugin::libdecor_plugin_description, constructor) == 4 * PTR);
+    assert!(offset_of!(plugin::libdecor_plugin_description, conflicting_symbols) == 5 * PTR);
+    assert!(size_of::<plugin::libdecor_plugin_description>() == (5 + 1024) * PTR);
+};
@@ -0,0 +1,150 @@
+/*
+ * The declarations of libdecor 0.2.2's src/libdecor-plugin.h, which the ABI test compiles
+ * against. libdecor doesn't install this header.
+ *
+ * libdecor is distributed under the MIT license. See
+ * https://gitlab.freedesktop.org/libdecor/libdecor for the original header.
+ */
+
+#ifndef LIBDECOR_PLUGIN_H
+#define LIBDECOR_PLUGIN_H
+
+#include "libdecor.h"
+
+struct libdecor_frame_private;
+
+struct libdecor_frame {
+	struct libdecor_frame_private *priv;
+	struct wl_list link;
+};
+
+struct libdecor_plugin_private;
+
+struct libdecor_plugin {
+	struct libdecor_plugin_private *priv;
+};
+
+typedef struct libdecor_plugin * (* libdecor_plugin_constructor)(struct libdecor *context);
+
+#define LIBDECOR_PLUGIN_PRIORITY_HIGH 1000
+#define LIBDECOR_PLUGIN_PRIORITY_MEDIUM 100
+#define LIBDECOR_PLUGIN_PRIORITY_LOW 0
+
+struct libdecor_plugin_priority {
+	const char *desktop;
+	int priority;
+};
+
+enum libdecor_plugin_capabilities {
+	LIBDECOR_PLUGIN_CAPABILITY_BASE = 1 << 0,
+};
+
+struct libdecor_plugin_description {
+	int api_version;
+	char *description;
+	enum libdecor_plugin_capabilities capabilities;
+	const struct libdecor_plugin_priority *priorities;
+	libdecor_plugin_constructor constructor;
+	char *conflicting_symbols[1024];
+};
+
+struct libdecor_plugin_interface {
+	void (* destroy)(struct libdecor_plugin *plugin);
+
+	int (* get_fd)(struct libdecor_plugin *plugin);
+	int (* dispatch)(struct libdecor_plugin *plugin,
+			 int timeout);
+
+	void (* set_handle_application_cursor)(struct libdecor_plugin *plugin,
+					       bool handle_cursor);
+
+	struct libdecor_frame * (* frame_new)(struct libdecor_plugin *plugin);
+	void (* frame_free)(struct libdecor_plugin *plugin,
+			    struct libdecor_frame *frame);
+	void (* frame_commit)(struct libdecor_plugin *plugin,
+			      struct libdecor_frame *frame,
+			      struct libdecor_state *state,
+			      struct libdecor_configuration *configuration);
+	void (*frame_property_changed)(struct libdecor_plugin *plugin,
+				       struct libdecor_frame *frame);
+	void (* frame_popup_grab)(struct libdecor_plugin *plugin,
+				  struct libdecor_frame *frame,
+				  const char *seat_name);
+	void (* frame_popup_ungrab)(struct libdecor_plugin *plugin,
+				    struct libdecor_frame *frame,
+				    const char *seat_name);
+
+	bool (* frame_get_border_size)(struct libdecor_plugin *plugin,
+				       struct libdecor_frame *frame,
+				       struct libdecor_configuration *configuration,
+				       int *left,
+				       int *right,
+				       int *top,
+				       int *bottom);
+
+	void (* reserved0)(void);
+	void (* reserved1)(void);
+	void (* reserved2)(void);
+	void (* reserved3)(void);
+	void (* reserved4)(void);
+	void (* reserved5)(void);
+	void (* reserved6)(void);
+	void (* reserved7)(void);
+	void (* reserved8)(void);
+	void (* reserved9)(void);
+};
+
+#define LIBDECOR_PLUGIN_API_VERSION 1
+
+struct wl_surface *
+libdecor_frame_get_wl_surface(struct libdecor_frame *frame);
+
+int
+libdecor_frame_get_content_width(struct libdecor_frame *frame);
+
+int
+libdecor_frame_get_content_height(struct libdecor_frame *frame);
+
+enum libdecor_window_state
+libdecor_frame_get_window_state(struct libdecor_frame *frame);
+
+enum libdecor_capabilities
+libdecor_frame_get_capabilities(const struct libdecor_frame *frame);
+
+void
+libdecor_frame_dismiss_popup(struct libdecor_frame *frame,
+			     const char *seat_name);
+
+void
+libdecor_frame_toplevel_commit(struct libdecor_frame *frame);
+
+struct wl_display *
+libdecor_get_wl_display(struct libdecor *context);
+
+void
+libdecor_notify_plugin_ready(struct libdecor *context);
+
+void
+libdecor_notify_plugin_error(struct libdecor *context,
+			     enum libdecor_error error,
+			     const char *__restrict fmt,
+			     ...);
+
+int
+libdecor_state_get_content_width(struct libdecor_state *state);
+
+int
+libdecor_state_get_content_height(struct libdecor_state *state);
+
+enum libdecor_window_state
+libdecor_state_get_window_state(struct libdecor_state *state);
+
+int
+libdecor_plugin_init(struct libdecor_plugin *plugin,
+		     struct libdecor *context,
+		     struct libdecor_plugin_interface *iface);
+
+void
+libdecor_plugin_release(struct libdecor_plugin *plugin);
+
+#endif /* LIBDECOR_PLUGIN_H */
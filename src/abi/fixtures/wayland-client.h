@@ -0,0 +1,21 @@
+/*
+ * Stand-in for libwayland's wayland-client.h, declaring only what libdecor.h and
+ * libdecor-plugin.h use, so the ABI test doesn't need libwayland's development files.
+ */
+
+#ifndef WAYLAND_CLIENT_H
+#define WAYLAND_CLIENT_H
+
+#include <stdint.h>
+
+struct wl_display;
+struct wl_output;
+struct wl_seat;
+struct wl_surface;
+
+struct wl_list {
+	struct wl_list *prev;
+	struct wl_list *next;
+};
+
+#endif
@@ -0,0 +1,218 @@
+use std::{
+    env, fs,
+    io::Write,
+    mem::{offset_of, size_of},
+    process::{self, Command},
+};
+
+use crate::{libdecor::*, libdecor_plugin as plugin};
+
+macro_rules! layout {
+    ($($c:literal => $rust:expr,)*) => {
+        &[$(($c, $rust as i64)),*]
+    };
+}
+
+const LAYOUT: &[(&str, i64)] = layout! {
+    "sizeof(struct libdecor_interface)" => size_of::<libdecor_interface>(),
+    "offsetof(struct libdecor_interface, error)" => offset_of!(libdecor_interface, error),
+    "offsetof(struct libdecor_interface, reserved0)" => offset_of!(libdecor_interface, reserved0),
+    "offsetof(struct libdecor_interface, reserved1)" => offset_of!(libdecor_interface, reserved1),
+    "offsetof(struct libdecor_interface, reserved2)" => offset_of!(libdecor_interface, reserved2),
+    "offsetof(struct libdecor_interface, reserved3)" => offset_of!(libdecor_interface, reserved3),
+    "offsetof(struct libdecor_interface, reserved4)" => offset_of!(libdecor_interface, reserved4),
+    "offsetof(struct libdecor_interface, reserved5)" => offset_of!(libdecor_interface, reserved5),
+    "offsetof(struct libdecor_interface, reserved6)" => offset_of!(libdecor_interface, reserved6),
+    "offsetof(struct libdecor_interface, reserved7)" => offset_of!(libdecor_interface, reserved7),
+    "offsetof(struct libdecor_interface, reserved8)" => offset_of!(libdecor_interface, reserved8),
+    "offsetof(struct libdecor_interface, reserved9)" => offset_of!(libdecor_interface, reserved9),
+    "sizeof(struct libdecor_frame_interface)" => size_of::<libdecor_frame_interface>(),
+    "offsetof(struct libdecor_frame_interface, configure)" => offset_of!(libdecor_frame_interface, configure),
+    "offsetof(struct libdecor_frame_interface, close)" => offset_of!(libdecor_frame_interface, close),
+    "offsetof(struct libdecor_frame_interface, commit)" => offset_of!(libdecor_frame_interface, commit),
+    "offsetof(struct libdecor_frame_interface, dismiss_popup)" => offset_of!(libdecor_frame_interface, dismiss_popup),
+    "offsetof(struct libdecor_frame_interface, reserved0)" => offset_of!(libdecor_frame_interface, reserved0),
+    "offsetof(struct libdecor_frame_interface, reserved1)" => offset_of!(libdecor_frame_interface, reserved1),
+    "offsetof(struct libdecor_frame_interface, reserved2)" => offset_of!(libdecor_frame_interface, reserved2),
+    "offsetof(struct libdecor_frame_interface, reserved3)" => offset_of!(libdecor_frame_interface, reserved3),
+    "offsetof(struct libdecor_frame_interface, reserved4)" => offset_of!(libdecor_frame_interface, reserved4),
+    "offsetof(struct libdecor_frame_interface, reserved5)" => offset_of!(libdecor_frame_interface, reserved5),
+    "offsetof(struct libdecor_frame_interface, reserved6)" => offset_of!(libdecor_frame_interface, reserved6),
+    "offsetof(struct libdecor_frame_interface, reserved7)" => offset_of!(libdecor_frame_interface, reserved7),
+    "offsetof(struct libdecor_frame_interface, reserved8)" => offset_of!(libdecor_frame_interface, reserved8),
+    "offsetof(struct libdecor_frame_interface, reserved9)" => offset_of!(libdecor_frame_interface, reserved9),
+    "sizeof(enum libdecor_capabilities)" => size_of::<libdecor_capabilities>(),
+    "sizeof(enum libdecor_error)" => size_of::<libdecor_error>(),
+    "sizeof(enum libdecor_resize_edge)" => size_of::<libdecor_resize_edge>(),
+    "sizeof(enum libdecor_window_state)" => size_of::<libdecor_window_state>(),
+    "LIBDECOR_ACTION_CLOSE" => LIBDECOR_ACTION_CLOSE,
+    "LIBDECOR_ACTION_FULLSCREEN" => LIBDECOR_ACTION_FULLSCREEN,
+    "LIBDECOR_ACTION_MINIMIZE" => LIBDECOR_ACTION_MINIMIZE,
+    "LIBDECOR_ACTION_MOVE" => LIBDECOR_ACTION_MOVE,
+    "LIBDECOR_ACTION_RESIZE" => LIBDECOR_ACTION_RESIZE,
+    "LIBDECOR_ERROR_COMPOSITOR_INCOMPATIBLE" => LIBDECOR_ERROR_COMPOSITOR_INCOMPATIBLE,
+    "LIBDECOR_ERROR_INVALID_FRAME_CONFIGURATION" => LIBDECOR_ERROR_INVALID_FRAME_CONFIGURATION,
+    "LIBDECOR_RESIZE_EDGE_BOTTOM" => LIBDECOR_RESIZE_EDGE_BOTTOM,
+    "LIBDECOR_RESIZE_EDGE_BOTTOM_LEFT" => LIBDECOR_RESIZE_EDGE_BOTTOM_LEFT,
+    "LIBDECOR_RESIZE_EDGE_BOTTOM_RIGHT" => LIBDECOR_RESIZE_EDGE_BOTTOM_RIGHT,
+    "LIBDECOR_RESIZE_EDGE_LEFT" => LIBDECOR_RESIZE_EDGE_LEFT,
+    "LIBDECOR_RESIZE_EDGE_NONE" => LIBDECOR_RESIZE_EDGE_NONE,
+    "LIBDECOR_RESIZE_EDGE_RIGHT" => LIBDECOR_RESIZE_EDGE_RIGHT,
+    "LIBDECOR_RESIZE_EDGE_TOP" => LIBDECOR_RESIZE_EDGE_TOP,
+    "LIBDECOR_RESIZE_EDGE_TOP_LEFT" => LIBDECOR_RESIZE_EDGE_TOP_LEFT,
+    "LIBDECOR_RESIZE_EDGE_TOP_RIGHT" => LIBDECOR_RESIZE_EDGE_TOP_RIGHT,
+    "LIBDECOR_WINDOW_STATE_ACTIVE" => LIBDECOR_WINDOW_STATE_ACTIVE,
+    "LIBDECOR_WINDOW_STATE_FULLSCREEN" => LIBDECOR_WINDOW_STATE_FULLSCREEN,
+    "LIBDECOR_WINDOW_STATE_MAXIMIZED" => LIBDECOR_WINDOW_STATE_MAXIMIZED,
+    "LIBDECOR_WINDOW_STATE_NONE" => LIBDECOR_WINDOW_STATE_NONE,
+    "LIBDECOR_WINDOW_STATE_TILED_BOTTOM" => LIBDECOR_WINDOW_STATE_TILED_BOTTOM,
+    "LIBDECOR_WINDOW_STATE_TILED_LEFT" => LIBDECOR_WINDOW_STATE_TILED_LEFT,
+    "LIBDECOR_WINDOW_STATE_TILED_RIGHT" => LIBDECOR_WINDOW_STATE_TILED_RIGHT,
+    "LIBDECOR_WINDOW_STATE_TILED_TOP" => LIBDECOR_WINDOW_STATE_TILED_TOP,
+};
+
+#[cfg(feature = "libdecor-0-2-0")]
+const LAYOUT_0_2_0: &[(&str, i64)] = layout! {
+    "LIBDECOR_WINDOW_STATE_SUSPENDED" => LIBDECOR_WINDOW_STATE_SUSPENDED,
+};
+
+#[cfg(not(feature = "libdecor-0-2-0"))]
+const LAYOUT_0_2_0: &[(&str, i64)] = &[];
+
+const PLUGIN_LAYOUT: &[(&str, i64)] = layout! {
+    "sizeof(struct libdecor_frame)" => size_of::<plugin::libdecor_frame>(),
+    "offsetof(struct libdecor_frame, priv)" => offset_of!(plugin::libdecor_frame, r#priv),
+    "offsetof(struct libdecor_frame, link)" => offset_of!(plugin::libdecor_frame, link),
+    "sizeof(struct libdecor_plugin)" => size_of::<plugin::libdecor_plugin>(),
+    "offsetof(struct libdecor_plugin, priv)" => offset_of!(plugin::libdecor_plugin, r#priv),
+    "sizeof(struct libdecor_plugin_priority)" => size_of::<plugin::libdecor_plugin_priority>(),
+    "offsetof(struct libdecor_plugin_priority, desktop)" => offset_of!(plugin::libdecor_plugin_priority, desktop),
+    "offsetof(struct libdecor_plugin_priority, priority)" => offset_of!(plugin::libdecor_plugin_priority, priority),
+    "sizeof(struct libdecor_plugin_description)" => size_of::<plugin::libdecor_plugin_description>(),
+    "offsetof(struct libdecor_plugin_description, api_version)" => offset_of!(plugin::libdecor_plugin_description, api_version),
+    "offsetof(struct libdecor_plugin_description, description)" => offset_of!(plugin::libdecor_plugin_description, description),
+    "offsetof(struct libdecor_plugin_description, capabilities)" => offset_of!(plugin::libdecor_plugin_description, capabilities),
+    "offsetof(struct libdecor_plugin_description, priorities)" => offset_of!(plugin::libdecor_plugin_description, priorities),
+    "offsetof(struct libdecor_plugin_description, constructor)" => offset_of!(plugin::libdecor_plugin_description, constructor),
+    "offsetof(struct libdecor_plugin_description, conflicting_symbols)" => offset_of!(plugin::libdecor_plugin_description, conflicting_symbols),
+    "sizeof(struct libdecor_plugin_interface)" => size_of::<plugin::libdecor_plugin_interface>(),
+    "offsetof(struct libdecor_plugin_interface, destroy)" => offset_of!(plugin::libdecor_plugin_interface, destroy),
+    "offsetof(struct libdecor_plugin_interface, get_fd)" => offset_of!(plugin::libdecor_plugin_interface, get_fd),
+    "offsetof(struct libdecor_plugin_interface, dispatch)" => offset_of!(plugin::libdecor_plugin_interface, dispatch),
+    "offsetof(struct libdecor_plugin_interface, set_handle_application_cursor)" => offset_of!(plugin::libdecor_plugin_interface, set_handle_application_cursor),
+    "offsetof(struct libdecor_plugin_interface, frame_new)" => offset_of!(plugin::libdecor_plugin_interface, frame_new),
+    "offsetof(struct libdecor_plugin_interface, frame_free)" => offset_of!(plugin::libdecor_plugin_interface, frame_free),
+    "offsetof(struct libdecor_plugin_interface, frame_commit)" => offset_of!(plugin::libdecor_plugin_interface, frame_commit),
+    "offsetof(struct libdecor_plugin_interface, frame_property_changed)" => offset_of!(plugin::libdecor_plugin_interface, frame_property_changed),
+    "offsetof(struct libdecor_plugin_interface, frame_popup_grab)" => offset_of!(plugin::libdecor_plugin_interface, frame_popup_grab),
+    "offsetof(struct libdecor_plugin_interface, frame_popup_ungrab)" => offset_of!(plugin::libdecor_plugin_interface, frame_popup_ungrab),
+    "offsetof(struct libdecor_plugin_interface, frame_get_border_size)" => offset_of!(plugin::libdecor_plugin_interface, frame_get_border_size),
+    "offsetof(struct libdecor_plugin_interface, reserved0)" => offset_of!(plugin::libdecor_plugin_interface, reserved0),
+    "offsetof(struct libdecor_plugin_interface, reserved1)" => offset_of!(plugin::libdecor_plugin_interface, reserved1),
+    "offsetof(struct libdecor_plugin_interface, reserved2)" => offset_of!(plugin::libdecor_plugin_interface, reserved2),
+    "offsetof(struct libdecor_plugin_interface, reserved3)" => offset_of!(plugin::libdecor_plugin_interface, reserved3),
+    "offsetof(struct libdecor_plugin_interface, reserved4)" => offset_of!(plugin::libdecor_plugin_interface, reserved4),
+    "offsetof(struct libdecor_plugin_interface, reserved5)" => offset_of!(plugin::libdecor_plugin_interface, reserved5),
+    "offsetof(struct libdecor_plugin_interface, reserved6)" => offset_of!(plugin::libdecor_plugin_interface, reserved6),
+    "offsetof(struct libdecor_plugin_interface, reserved7)" => offset_of!(plugin::libdecor_plugin_interface, reserved7),
+    "offsetof(struct libdecor_plugin_interface, reserved8)" => offset_of!(plugin::libdecor_plugin_interface, reserved8),
+    "offsetof(struct libdecor_plugin_interface, reserved9)" => offset_of!(plugin::libdecor_plugin_interface, reserved9),
+    "sizeof(enum libdecor_plugin_capabilities)" => size_of::<plugin::libdecor_plugin_capabilities>(),
+    "LIBDECOR_PLUGIN_API_VERSION" => plugin::LIBDECOR_PLUGIN_API_VERSION,
+    "LIBDECOR_PLUGIN_CAPABILITY_BASE" => plugin::LIBDECOR_PLUGIN_CAPABILITY_BASE,
+    "LIBDECOR_PLUGIN_PRIORITY_HIGH" => plugin::LIBDECOR_PLUGIN_PRIORITY_HIGH,
+    "LIBDECOR_PLUGIN_PRIORITY_LOW" => plugin::LIBDECOR_PLUGIN_PRIORITY_LOW,
+    "LIBDECOR_PLUGIN_PRIORITY_MEDIUM" => plugin::LIBDECOR_PLUGIN_PRIORITY_MEDIUM,
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/abi/fixtures");
+
+/// Compiles a C program against libdecor 0.2.2's `libdecor.h`, kept in `src/abi/fixtures`, and
+/// compares what it prints with the Rust declarations.
+#[test]
+fn matches_c_header() {
+    let cflags = [format!("-I{FIXTURES}")];
+    compare(
+        "libdecor",
+        "libdecor.h",
+        &cflags,
+        &[LAYOUT, LAYOUT_0_2_0].concat(),
+    );
+}
+
+/// Like [`matches_c_header`] for `libdecor-plugin.h`.
+#[test]
+fn plugin_matches_c_header() {
+    let cflags = [format!("-I{FIXTURES}")];
+    compare("plugin", "libdecor-plugin.h", &cflags, PLUGIN_LAYOUT);
+}
+
+/// Like [`matches_c_header`] against the `libdecor.h` pkg-config finds, to check the bindings
+/// against whatever release is installed. Run it with `cargo test -- --ignored`.
+#[test]
+#[ignore = "requires libdecor-0's development files"]
+fn matches_installed_c_header() {
+    let cflags = pkg_config_cflags("libdecor-0");
+    compare(
+        "installed",
+        "libdecor.h",
+        &cflags,
+        &[LAYOUT, LAYOUT_0_2_0].concat(),
+    );
+}
+
+fn pkg_config_cflags(package: &str) -> Vec<String> {
+    let output = Command::new("pkg-config")
+        .args(["--cflags", package])
+        .output()
+        .expect("failed to run pkg-config");
+    assert!(
+        output.status.success(),
+        "pkg-config couldn't find {package}"
+    );
+    let cflags = String::from_utf8(output.stdout).unwrap();
+    cflags.split_whitespace().map(String::from).collect()
+}
+
+fn compare(name: &str, header: &str, cflags: &[String], entries: &[(&str, i64)]) {
+    let dir = env::temp_dir().join(format!("libdecor-headers-abi-{}-{name}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("abi.c");
+    let binary = dir.join("abi");
+
+    let mut c = fs::File::create(&source).unwrap();
+    writeln!(c, "#include <stddef.h>").unwrap();
+    writeln!(c, "#include <stdio.h>").unwrap();
+    writeln!(c, "#include <{header}>").unwrap();
+    writeln!(c, "int main(void) {{").unwrap();
+    for (expr, _) in entries {
+        writeln!(c, "    printf(\"%lld\\n\", (long long)({expr}));").unwrap();
+    }
+    writeln!(c, "    return 0;").unwrap();
+    writeln!(c, "}}").unwrap();
+    drop(c);
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".into());
+    let status = Command::new(cc)
+        .args(cflags)
+        .arg(&source)
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to compile {}", source.display());
+
+    let output = Command::new(&binary).output().unwrap();
+    assert!(output.status.success());
+    let output = String::from_utf8(output.stdout).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(output.lines().count(), entries.len());
+    let mismatches: Vec<_> = entries
+        .iter()
+        .zip(output.lines())
+        .filter(|((_, rust), c)| c.parse::<i64>() != Ok(*rust))
+        .map(|((expr, rust), c)| format!("{expr}: C {c}, Rust {rust}"))
+        .collect();
+    assert!(mismatches.is_empty(), "{mismatches:#?}");
+}
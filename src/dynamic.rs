@@ -0,0 +1,164 @@
+use alloc::ffi::CString;
+use core::{
+    error::Error,
+    ffi::{CStr, c_char, c_int, c_void},
+    fmt,
+    mem::transmute,
+    ptr::NonNull,
+};
+
+use libc::{RTLD_LOCAL, RTLD_NOW, dlclose, dlerror, dlopen, dlsym};
+use wayland_headers::{wayland_client::*, xdg_shell_client_protocol::*};
+
+use crate::libdecor::{with_functions, *};
+
+#[cfg(test)]
+mod test;
+
+/// The soname [`LibdecorFunctions::load`] passes to `dlopen`.
+pub const LIBDECOR_SONAME: &CStr = c"libdecor-0.so.0";
+
+macro_rules! libdecor_functions {
+    ($(
+        $(#[doc = $doc:literal])*
+        $(#[cfg($cfg:meta)])?
+        pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;
+    )*) => {
+        /// Every function declared in [`crate::libdecor`], resolved at runtime with `dlsym`.
+        ///
+        /// The library is closed when the table is dropped, so the function pointers must not be
+        /// copied out and called after that.
+        pub struct LibdecorFunctions {
+            handle: NonNull<c_void>,
+            $($(#[doc = $doc])* $(#[cfg($cfg)])? pub $name: unsafe extern "C" fn($($arg: $ty),*) $(-> $ret)?,)*
+        }
+
+        const SYMBOLS: &[&str] = &[$($(#[cfg($cfg)])? stringify!($name)),*];
+
+        impl LibdecorFunctions {
+            /// Opens `libdecor-0.so.0` and resolves every function.
+            ///
+            /// # Safety
+            ///
+            /// Loading a library runs its initializers, and the resolved symbols are assumed to
+            /// have the signatures declared in [`crate::libdecor`].
+            pub unsafe fn load() -> Result<Self, LoadError> {
+                unsafe { Self::load_from(LIBDECOR_SONAME) }
+            }
+
+            /// Opens `filename` and resolves every function.
+            ///
+            /// # Safety
+            ///
+            /// See [`LibdecorFunctions::load`].
+            pub unsafe fn load_from(filename: &CStr) -> Result<Self, LoadError> {
+                let handle = unsafe { dlopen(filename.as_ptr(), RTLD_NOW | RTLD_LOCAL) };
+                let Some(handle) = NonNull::new(handle) else {
+                    let message = unsafe { dlerror() };
+                    let message = match message.is_null() {
+                        true => CString::default(),
+                        false => unsafe { CStr::from_ptr(message) }.into(),
+                    };
+                    return Err(LoadError::Open(message));
+                };
+
+                $($(#[cfg($cfg)])? let $name = unsafe {
+                    dlsym(handle.as_ptr(), concat!(stringify!($name), "\0").as_ptr().cast())
+                };)*
+
+                let mut missing = MissingSymbols(0);
+                for (index, symbol) in [$($(#[cfg($cfg)])? $name),*].into_iter().enumerate() {
+                    if symbol.is_null() {
+                        missing.0 |= 1 << index;
+                    }
+                }
+                if missing.0 != 0 {
+                    unsafe { dlclose(handle.as_ptr()) };
+                    return Err(LoadError::MissingSymbols(missing));
+                }
+
+                Ok(Self {
+                    handle,
+                    $($(#[cfg($cfg)])? $name: unsafe {
+                        transmute::<*mut c_void, unsafe extern "C" fn($($ty),*) $(-> $ret)?>($name)
+                    },)*
+                })
+            }
+        }
+    };
+}
+
+with_functions!(libdecor_functions);
+
+const _: () = assert!(SYMBOLS.len() <= u64::BITS as usize);
+
+impl Drop for LibdecorFunctions {
+    fn drop(&mut self) {
+        unsafe { dlclose(self.handle.as_ptr()) };
+    }
+}
+
+#[cfg(feature = "probe")]
+impl LibdecorFunctions {
+    /// Probes the loaded library.
+    pub fn probe(&self) -> crate::probe::LibdecorInfo {
+        let info = unsafe { crate::probe::probe_handle(self.handle.as_ptr()) };
+        info.expect("libdecor_new was resolved when loading")
+    }
+}
+
+unsafe impl Send for LibdecorFunctions {}
+unsafe impl Sync for LibdecorFunctions {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadError {
+    /// `dlopen` could not find or load the library, with the message from `dlerror`.
+    Open(CString),
+    /// The library was opened but doesn't export every expected function.
+    MissingSymbols(MissingSymbols),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open(message) => write!(
+                f,
+                "failed to open the libdecor library: {}",
+                message.to_string_lossy()
+            ),
+            Self::MissingSymbols(missing) => missing.fmt(f),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// The set of functions a [`LoadError::MissingSymbols`] couldn't resolve.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct MissingSymbols(u64);
+
+impl MissingSymbols {
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        SYMBOLS
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| self.0 & (1 << index) != 0)
+            .map(|(_, &symbol)| symbol)
+    }
+}
+
+impl fmt::Debug for MissingSymbols {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl fmt::Display for MissingSymbols {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("libdecor is missing symbols:")?;
+        for symbol in self.iter() {
+            write!(f, " {symbol}")?;
+        }
+        Ok(())
+    }
+}
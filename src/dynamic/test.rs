@@ -0,0 +1,36 @@
+use std::{string::ToString, vec::Vec};
+
+use super::{LibdecorFunctions, LoadError, SYMBOLS};
+
+#[test]
+fn load_reports_dlerror() {
+    let Err(LoadError::Open(message)) =
+        (unsafe { LibdecorFunctions::load_from(c"libdecor-headers-missing.so") })
+    else {
+        panic!("expected LoadError::Open");
+    };
+    let message = message.to_str().unwrap();
+    assert!(message.contains("libdecor-headers-missing.so"), "{message}");
+
+    let error = LoadError::Open(c"oops".into());
+    assert_eq!(
+        error.to_string(),
+        "failed to open the libdecor library: oops"
+    );
+}
+
+#[test]
+fn load_reports_missing_symbols() {
+    let Err(LoadError::MissingSymbols(missing)) =
+        (unsafe { LibdecorFunctions::load_from(c"libc.so.6") })
+    else {
+        panic!("expected LoadError::MissingSymbols");
+    };
+    assert_eq!(missing.iter().collect::<Vec<_>>(), SYMBOLS);
+
+    let error = LoadError::MissingSymbols(missing).to_string();
+    assert!(
+        error.starts_with("libdecor is missing symbols: libdecor_configuration_get_content_size ")
+    );
+    assert!(error.ends_with(" libdecor_unref"));
+}
@@ -2,7 +2,27 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "dynamic")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod abi;
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
 pub mod libdecor;
+pub mod libdecor_plugin;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "probe")]
+pub mod probe;
+#[cfg(feature = "safe")]
+pub mod safe;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "weak")]
+pub mod weak;
 
-#[cfg(test)]
+// Runs against the real libdecor, which the mock's symbols would shadow.
+#[cfg(all(test, feature = "testing", not(feature = "mock")))]
 mod test;
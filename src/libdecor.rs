@@ -103,155 +103,204 @@ pub const LIBDECOR_WINDOW_STATE_ACTIVE: libdecor_window_state = 1 << 0;
 pub const LIBDECOR_WINDOW_STATE_FULLSCREEN: libdecor_window_state = 1 << 2;
 pub const LIBDECOR_WINDOW_STATE_MAXIMIZED: libdecor_window_state = 1 << 1;
 pub const LIBDECOR_WINDOW_STATE_NONE: libdecor_window_state = 0;
+/// Added in libdecor 0.2.0.
+#[cfg(feature = "libdecor-0-2-0")]
 pub const LIBDECOR_WINDOW_STATE_SUSPENDED: libdecor_window_state = 1 << 7;
 pub const LIBDECOR_WINDOW_STATE_TILED_BOTTOM: libdecor_window_state = 1 << 6;
 pub const LIBDECOR_WINDOW_STATE_TILED_LEFT: libdecor_window_state = 1 << 3;
 pub const LIBDECOR_WINDOW_STATE_TILED_RIGHT: libdecor_window_state = 1 << 4;
 pub const LIBDECOR_WINDOW_STATE_TILED_TOP: libdecor_window_state = 1 << 5;
 
-unsafe extern "C" {
-    pub fn libdecor_configuration_get_content_size(
-        configuration: *mut libdecor_configuration,
-        frame: *mut libdecor_frame,
-        width: *mut c_int,
-        height: *mut c_int,
-    ) -> bool;
+/// Invokes `$callback!` with every function declaration, so the `extern` block below and the
+/// `dynamic` module's table are generated from the same list.
+macro_rules! with_functions {
+    ($callback:ident) => {
+        $callback! {
+            pub fn libdecor_configuration_get_content_size(
+                configuration: *mut libdecor_configuration,
+                frame: *mut libdecor_frame,
+                width: *mut c_int,
+                height: *mut c_int,
+            ) -> bool;
+
+            pub fn libdecor_configuration_get_window_state(
+                configuration: *mut libdecor_configuration,
+                window_state: *mut libdecor_window_state,
+            ) -> bool;
+
+            pub fn libdecor_decorate(
+                context: *mut libdecor,
+                surface: *mut wl_surface,
+                iface: *mut libdecor_frame_interface,
+                user_data: *mut c_void,
+            ) -> *mut libdecor_frame;
+
+            pub fn libdecor_dispatch(context: *mut libdecor, timeout: c_int) -> c_int;
 
-    pub fn libdecor_configuration_get_window_state(
-        configuration: *mut libdecor_configuration,
-        window_state: *mut libdecor_window_state,
-    ) -> bool;
+            pub fn libdecor_frame_close(frame: *mut libdecor_frame);
+
+            pub fn libdecor_frame_commit(
+                frame: *mut libdecor_frame,
+                state: *mut libdecor_state,
+                configuration: *mut libdecor_configuration,
+            );
+
+            /// Added in libdecor 0.1.1.
+            #[cfg(feature = "libdecor-0-1-1")]
+            pub fn libdecor_frame_get_max_content_size(
+                frame: *const libdecor_frame,
+                content_width: *mut c_int,
+                content_height: *mut c_int,
+            );
+
+            /// Added in libdecor 0.1.1.
+            #[cfg(feature = "libdecor-0-1-1")]
+            pub fn libdecor_frame_get_min_content_size(
+                frame: *const libdecor_frame,
+                content_width: *mut c_int,
+                content_height: *mut c_int,
+            );
+
+            pub fn libdecor_frame_get_title(frame: *mut libdecor_frame) -> *const c_char;
+
+            pub fn libdecor_frame_get_xdg_surface(frame: *mut libdecor_frame) -> *mut xdg_surface;
+
+            pub fn libdecor_frame_get_xdg_toplevel(frame: *mut libdecor_frame) -> *mut xdg_toplevel;
+
+            pub fn libdecor_frame_has_capability(
+                frame: *mut libdecor_frame,
+                capability: libdecor_capabilities,
+            ) -> bool;
+
+            pub fn libdecor_frame_is_floating(frame: *mut libdecor_frame) -> bool;
+
+            pub fn libdecor_frame_is_visible(frame: *mut libdecor_frame) -> bool;
 
-    pub fn libdecor_decorate(
-        context: *mut libdecor,
-        surface: *mut wl_surface,
-        iface: *mut libdecor_frame_interface,
-        user_data: *mut c_void,
-    ) -> *mut libdecor_frame;
+            pub fn libdecor_frame_map(frame: *mut libdecor_frame);
+
+            pub fn libdecor_frame_move(
+                frame: *mut libdecor_frame,
+                wl_seat: *mut wl_seat,
+                serial: u32,
+            );
+
+            pub fn libdecor_frame_popup_grab(frame: *mut libdecor_frame, seat_name: *const c_char);
+
+            pub fn libdecor_frame_popup_ungrab(
+                frame: *mut libdecor_frame,
+                seat_name: *const c_char,
+            );
+
+            pub fn libdecor_frame_ref(frame: *mut libdecor_frame);
+
+            pub fn libdecor_frame_resize(
+                frame: *mut libdecor_frame,
+                wl_seat: *mut wl_seat,
+                serial: u32,
+                edge: libdecor_resize_edge,
+            );
+
+            pub fn libdecor_frame_set_app_id(frame: *mut libdecor_frame, app_id: *const c_char);
+
+            pub fn libdecor_frame_set_capabilities(
+                frame: *mut libdecor_frame,
+                capabilities: libdecor_capabilities,
+            );
+
+            pub fn libdecor_frame_set_fullscreen(
+                frame: *mut libdecor_frame,
+                output: *mut wl_output,
+            );
+
+            pub fn libdecor_frame_set_max_content_size(
+                frame: *mut libdecor_frame,
+                content_width: c_int,
+                content_height: c_int,
+            );
 
-    pub fn libdecor_dispatch(context: *mut libdecor, timeout: c_int) -> c_int;
+            pub fn libdecor_frame_set_maximized(frame: *mut libdecor_frame);
 
-    pub fn libdecor_frame_close(frame: *mut libdecor_frame);
+            pub fn libdecor_frame_set_min_content_size(
+                frame: *mut libdecor_frame,
+                content_width: c_int,
+                content_height: c_int,
+            );
 
-    pub fn libdecor_frame_commit(
-        frame: *mut libdecor_frame,
-        state: *mut libdecor_state,
-        configuration: *mut libdecor_configuration,
-    );
+            pub fn libdecor_frame_set_minimized(frame: *mut libdecor_frame);
 
-    pub fn libdecor_frame_get_max_content_size(
-        frame: *const libdecor_frame,
-        content_width: *mut c_int,
-        content_height: *mut c_int,
-    );
+            pub fn libdecor_frame_set_parent(
+                frame: *mut libdecor_frame,
+                parent: *mut libdecor_frame,
+            );
 
-    pub fn libdecor_frame_get_min_content_size(
-        frame: *const libdecor_frame,
-        content_width: *mut c_int,
-        content_height: *mut c_int,
-    );
+            pub fn libdecor_frame_set_title(frame: *mut libdecor_frame, title: *const c_char);
 
-    pub fn libdecor_frame_get_title(frame: *mut libdecor_frame) -> *const c_char;
+            pub fn libdecor_frame_set_visibility(frame: *mut libdecor_frame, visible: bool);
 
-    pub fn libdecor_frame_get_xdg_surface(frame: *mut libdecor_frame) -> *mut xdg_surface;
+            pub fn libdecor_frame_show_window_menu(
+                frame: *mut libdecor_frame,
+                wl_seat: *mut wl_seat,
+                serial: u32,
+                x: c_int,
+                y: c_int,
+            );
 
-    pub fn libdecor_frame_get_xdg_toplevel(frame: *mut libdecor_frame) -> *mut xdg_toplevel;
+            pub fn libdecor_frame_translate_coordinate(
+                frame: *mut libdecor_frame,
+                surface_x: c_int,
+                surface_y: c_int,
+                frame_x: *mut c_int,
+                frame_y: *mut c_int,
+            );
 
-    pub fn libdecor_frame_has_capability(
-        frame: *mut libdecor_frame,
-        capability: libdecor_capabilities,
-    ) -> bool;
+            pub fn libdecor_frame_unref(frame: *mut libdecor_frame);
 
-    pub fn libdecor_frame_is_floating(frame: *mut libdecor_frame) -> bool;
+            pub fn libdecor_frame_unset_capabilities(
+                frame: *mut libdecor_frame,
+                capabilities: libdecor_capabilities,
+            );
 
-    pub fn libdecor_frame_is_visible(frame: *mut libdecor_frame) -> bool;
+            pub fn libdecor_frame_unset_fullscreen(frame: *mut libdecor_frame);
 
-    pub fn libdecor_frame_map(frame: *mut libdecor_frame);
+            pub fn libdecor_frame_unset_maximized(frame: *mut libdecor_frame);
 
-    pub fn libdecor_frame_move(frame: *mut libdecor_frame, wl_seat: *mut wl_seat, serial: u32);
+            pub fn libdecor_get_fd(context: *mut libdecor) -> c_int;
 
-    pub fn libdecor_frame_popup_grab(frame: *mut libdecor_frame, seat_name: *const c_char);
+            pub fn libdecor_new(
+                display: *mut wl_display,
+                iface: *mut libdecor_interface,
+            ) -> *mut libdecor;
 
-    pub fn libdecor_frame_popup_ungrab(frame: *mut libdecor_frame, seat_name: *const c_char);
+            pub fn libdecor_state_free(state: *mut libdecor_state);
 
-    pub fn libdecor_frame_ref(frame: *mut libdecor_frame);
+            pub fn libdecor_state_new(width: c_int, height: c_int) -> *mut libdecor_state;
 
-    pub fn libdecor_frame_resize(
-        frame: *mut libdecor_frame,
-        wl_seat: *mut wl_seat,
-        serial: u32,
-        edge: libdecor_resize_edge,
-    );
-
-    pub fn libdecor_frame_set_app_id(frame: *mut libdecor_frame, app_id: *const c_char);
-
-    pub fn libdecor_frame_set_capabilities(
-        frame: *mut libdecor_frame,
-        capabilities: libdecor_capabilities,
-    );
-
-    pub fn libdecor_frame_set_fullscreen(frame: *mut libdecor_frame, output: *mut wl_output);
-
-    pub fn libdecor_frame_set_max_content_size(
-        frame: *mut libdecor_frame,
-        content_width: c_int,
-        content_height: c_int,
-    );
-
-    pub fn libdecor_frame_set_maximized(frame: *mut libdecor_frame);
-
-    pub fn libdecor_frame_set_min_content_size(
-        frame: *mut libdecor_frame,
-        content_width: c_int,
-        content_height: c_int,
-    );
-
-    pub fn libdecor_frame_set_minimized(frame: *mut libdecor_frame);
-
-    pub fn libdecor_frame_set_parent(frame: *mut libdecor_frame, parent: *mut libdecor_frame);
-
-    pub fn libdecor_frame_set_title(frame: *mut libdecor_frame, title: *const c_char);
-
-    pub fn libdecor_frame_set_visibility(frame: *mut libdecor_frame, visible: bool);
-
-    pub fn libdecor_frame_show_window_menu(
-        frame: *mut libdecor_frame,
-        wl_seat: *mut wl_seat,
-        serial: u32,
-        x: c_int,
-        y: c_int,
-    );
-
-    pub fn libdecor_frame_translate_coordinate(
-        frame: *mut libdecor_frame,
-        surface_x: c_int,
-        surface_y: c_int,
-        frame_x: *mut c_int,
-        frame_y: *mut c_int,
-    );
-
-    pub fn libdecor_frame_unref(frame: *mut libdecor_frame);
-
-    pub fn libdecor_frame_unset_capabilities(
-        frame: *mut libdecor_frame,
-        capabilities: libdecor_capabilities,
-    );
-
-    pub fn libdecor_frame_unset_fullscreen(frame: *mut libdecor_frame);
-
-    pub fn libdecor_frame_unset_maximized(frame: *mut libdecor_frame);
-
-    pub fn libdecor_get_fd(context: *mut libdecor) -> c_int;
-
-    pub fn libdecor_new(display: *mut wl_display, iface: *mut libdecor_interface) -> *mut libdecor;
-
-    pub fn libdecor_state_free(state: *mut libdecor_state);
-
-    pub fn libdecor_state_new(width: c_int, height: c_int) -> *mut libdecor_state;
+            pub fn libdecor_unref(context: *mut libdecor);
+        }
+    };
+}
 
-    pub fn libdecor_unref(context: *mut libdecor);
+#[cfg(feature = "dynamic")]
+pub(crate) use with_functions;
+
+macro_rules! extern_functions {
+    ($(
+        $(#[doc = $doc:literal])*
+        $(#[cfg($cfg:meta)])?
+        pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;
+    )*) => {
+        unsafe extern "C" {
+            $(
+                $(#[doc = $doc])*
+                $(#[cfg($cfg)])?
+                pub fn $name($($arg: $ty),*) $(-> $ret)?;
+            )*
+        }
+    };
 }
 
+with_functions!(extern_functions);
+
 pub type libdecor_capabilities = c_int;
 pub type libdecor_error = c_int;
 pub type libdecor_resize_edge = c_int;
@@ -0,0 +1,172 @@
+use core::{
+    ffi::{c_char, c_int},
+    marker::{PhantomData, PhantomPinned},
+};
+
+use wayland_headers::{wayland_client::*, wayland_util::wl_list};
+
+#[doc(no_inline)]
+pub use super::libdecor::*;
+
+#[repr(C)]
+pub struct libdecor_frame {
+    pub r#priv: *mut libdecor_frame_private,
+    pub link: wl_list,
+}
+
+#[repr(C)]
+pub struct libdecor_frame_private {
+    _data: (),
+    _marker: PhantomData<(*mut u8, PhantomPinned)>,
+}
+
+#[repr(C)]
+pub struct libdecor_plugin {
+    pub r#priv: *mut libdecor_plugin_private,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct libdecor_plugin_description {
+    pub api_version: c_int,
+    pub description: *mut c_char,
+    pub capabilities: libdecor_plugin_capabilities,
+    pub priorities: *const libdecor_plugin_priority,
+    pub constructor: libdecor_plugin_constructor,
+    pub conflicting_symbols: [*mut c_char; 1024],
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct libdecor_plugin_interface {
+    pub destroy: Option<unsafe extern "C" fn(plugin: *mut libdecor_plugin)>,
+
+    pub get_fd: Option<unsafe extern "C" fn(plugin: *mut libdecor_plugin) -> c_int>,
+
+    pub dispatch:
+        Option<unsafe extern "C" fn(plugin: *mut libdecor_plugin, timeout: c_int) -> c_int>,
+
+    pub set_handle_application_cursor:
+        Option<unsafe extern "C" fn(plugin: *mut libdecor_plugin, handle_cursor: bool)>,
+
+    pub frame_new:
+        Option<unsafe extern "C" fn(plugin: *mut libdecor_plugin) -> *mut libdecor_frame>,
+
+    pub frame_free:
+        Option<unsafe extern "C" fn(plugin: *mut libdecor_plugin, frame: *mut libdecor_frame)>,
+
+    pub frame_commit: Option<
+        unsafe extern "C" fn(
+            plugin: *mut libdecor_plugin,
+            frame: *mut libdecor_frame,
+            state: *mut libdecor_state,
+            configuration: *mut libdecor_configuration,
+        ),
+    >,
+
+    pub frame_property_changed:
+        Option<unsafe extern "C" fn(plugin: *mut libdecor_plugin, frame: *mut libdecor_frame)>,
+
+    pub frame_popup_grab: Option<
+        unsafe extern "C" fn(
+            plugin: *mut libdecor_plugin,
+            frame: *mut libdecor_frame,
+            seat_name: *const c_char,
+        ),
+    >,
+
+    pub frame_popup_ungrab: Option<
+        unsafe extern "C" fn(
+            plugin: *mut libdecor_plugin,
+            frame: *mut libdecor_frame,
+            seat_name: *const c_char,
+        ),
+    >,
+
+    pub frame_get_border_size: Option<
+        unsafe extern "C" fn(
+            plugin: *mut libdecor_plugin,
+            frame: *mut libdecor_frame,
+            configuration: *mut libdecor_configuration,
+            left: *mut c_int,
+            right: *mut c_int,
+            top: *mut c_int,
+            bottom: *mut c_int,
+        ) -> bool,
+    >,
+
+    pub reserved0: Option<unsafe extern "C" fn()>,
+    pub reserved1: Option<unsafe extern "C" fn()>,
+    pub reserved2: Option<unsafe extern "C" fn()>,
+    pub reserved3: Option<unsafe extern "C" fn()>,
+    pub reserved4: Option<unsafe extern "C" fn()>,
+    pub reserved5: Option<unsafe extern "C" fn()>,
+    pub reserved6: Option<unsafe extern "C" fn()>,
+    pub reserved7: Option<unsafe extern "C" fn()>,
+    pub reserved8: Option<unsafe extern "C" fn()>,
+    pub reserved9: Option<unsafe extern "C" fn()>,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct libdecor_plugin_priority {
+    pub desktop: *const c_char,
+    pub priority: c_int,
+}
+
+#[repr(C)]
+pub struct libdecor_plugin_private {
+    _data: (),
+    _marker: PhantomData<(*mut u8, PhantomPinned)>,
+}
+
+pub const LIBDECOR_PLUGIN_API_VERSION: c_int = 1;
+pub const LIBDECOR_PLUGIN_CAPABILITY_BASE: libdecor_plugin_capabilities = 1 << 0;
+pub const LIBDECOR_PLUGIN_PRIORITY_HIGH: c_int = 1000;
+pub const LIBDECOR_PLUGIN_PRIORITY_LOW: c_int = 0;
+pub const LIBDECOR_PLUGIN_PRIORITY_MEDIUM: c_int = 100;
+
+unsafe extern "C" {
+    pub fn libdecor_frame_dismiss_popup(frame: *mut libdecor_frame, seat_name: *const c_char);
+
+    pub fn libdecor_frame_get_capabilities(frame: *const libdecor_frame) -> libdecor_capabilities;
+
+    pub fn libdecor_frame_get_content_height(frame: *mut libdecor_frame) -> c_int;
+
+    pub fn libdecor_frame_get_content_width(frame: *mut libdecor_frame) -> c_int;
+
+    pub fn libdecor_frame_get_window_state(frame: *mut libdecor_frame) -> libdecor_window_state;
+
+    pub fn libdecor_frame_get_wl_surface(frame: *mut libdecor_frame) -> *mut wl_surface;
+
+    pub fn libdecor_frame_toplevel_commit(frame: *mut libdecor_frame);
+
+    pub fn libdecor_get_wl_display(context: *mut libdecor) -> *mut wl_display;
+
+    pub fn libdecor_notify_plugin_error(
+        context: *mut libdecor,
+        error: libdecor_error,
+        fmt: *const c_char,
+        ...
+    );
+
+    pub fn libdecor_notify_plugin_ready(context: *mut libdecor);
+
+    pub fn libdecor_plugin_init(
+        plugin: *mut libdecor_plugin,
+        context: *mut libdecor,
+        iface: *mut libdecor_plugin_interface,
+    ) -> c_int;
+
+    pub fn libdecor_plugin_release(plugin: *mut libdecor_plugin);
+
+    pub fn libdecor_state_get_content_height(state: *mut libdecor_state) -> c_int;
+
+    pub fn libdecor_state_get_content_width(state: *mut libdecor_state) -> c_int;
+
+    pub fn libdecor_state_get_window_state(state: *mut libdecor_state) -> libdecor_window_state;
+}
+
+pub type libdecor_plugin_capabilities = c_int;
+pub type libdecor_plugin_constructor =
+    Option<unsafe extern "C" fn(context: *mut libdecor) -> *mut libdecor_plugin>;
@@ -0,0 +1,440 @@
+//! An in-process fake of libdecor for testing applications without a Wayland compositor.
+//!
+//! Enabling the `mock` feature defines every function declared in [`crate::libdecor`] in Rust, so
+//! the application must not also link against the real `libdecor-0`. Nothing talks to a
+//! compositor: `libdecor_dispatch` never blocks and only delivers the events scripted through
+//! [`MockContext`] and [`MockFrame`], while every request a frame receives is recorded as a
+//! [`Call`].
+//!
+//! Like libdecor, which only creates a frame's `xdg_surface` and `xdg_toplevel` once the context
+//! has finished initializing, `libdecor_frame_get_xdg_surface` and
+//! `libdecor_frame_get_xdg_toplevel` return null until the context's first `libdecor_dispatch`.
+//! After that they return placeholders that must not be passed to libwayland, unless the test
+//! provides real objects through [`MockFrame::set_shell_objects`].
+//!
+//! Mock objects live on the thread that created them, and [`contexts`] and [`frames`] only return
+//! those created by the current thread, so tests running in parallel don't see each other's
+//! objects.
+
+use core::{
+    cell::{Cell, RefCell},
+    ffi::{CStr, c_int, c_void},
+    ptr::null_mut,
+};
+use std::{
+    collections::VecDeque,
+    ffi::CString,
+    io::{ErrorKind, Read, Write},
+    os::{fd::AsRawFd, unix::net::UnixStream},
+    thread_local,
+    vec::Vec,
+};
+
+use wayland_headers::{
+    wayland_client::{wl_display, wl_output, wl_surface},
+    xdg_shell_client_protocol::{xdg_surface, xdg_toplevel},
+};
+
+use crate::libdecor::*;
+
+mod symbols;
+#[cfg(test)]
+mod test;
+
+/// A request made on a mocked `libdecor_frame`, in the order the application made it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Call {
+    Close,
+    Commit {
+        width: c_int,
+        height: c_int,
+        /// Whether a configuration was passed, acknowledging it.
+        configured: bool,
+    },
+    Map,
+    Move {
+        serial: u32,
+    },
+    PopupGrab {
+        seat: CString,
+    },
+    PopupUngrab {
+        seat: CString,
+    },
+    Resize {
+        serial: u32,
+        edge: libdecor_resize_edge,
+    },
+    SetAppId(CString),
+    SetCapabilities(libdecor_capabilities),
+    SetFullscreen {
+        output: *mut wl_output,
+    },
+    SetMaxContentSize(c_int, c_int),
+    SetMaximized,
+    SetMinContentSize(c_int, c_int),
+    SetMinimized,
+    SetParent(*mut libdecor_frame),
+    SetTitle(CString),
+    SetVisibility(bool),
+    ShowWindowMenu {
+        serial: u32,
+        x: c_int,
+        y: c_int,
+    },
+    UnsetCapabilities(libdecor_capabilities),
+    UnsetFullscreen,
+    UnsetMaximized,
+}
+
+/// Every live context created with `libdecor_new` on the current thread, oldest first.
+pub fn contexts() -> Vec<MockContext> {
+    LIVE.with_borrow(|live| {
+        live.contexts
+            .iter()
+            .map(|&raw| MockContext(raw.cast()))
+            .collect()
+    })
+}
+
+/// Every live frame created with `libdecor_decorate` on the current thread, oldest first.
+pub fn frames() -> Vec<MockFrame> {
+    LIVE.with_borrow(|live| {
+        live.frames
+            .iter()
+            .map(|&raw| MockFrame(raw.cast()))
+            .collect()
+    })
+}
+
+/// A handle to a mocked `libdecor` context.
+///
+/// Methods panic if the context has already been unreferenced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MockContext(*mut libdecor);
+
+impl MockContext {
+    pub fn as_raw(&self) -> *mut libdecor {
+        self.0
+    }
+
+    /// The display passed to `libdecor_new`.
+    pub fn display(&self) -> *mut wl_display {
+        self.data().display
+    }
+
+    /// The live frames decorated with this context, oldest first.
+    pub fn frames(&self) -> Vec<MockFrame> {
+        frames()
+            .into_iter()
+            .filter(|frame| frame.data().context == self.0.cast())
+            .collect()
+    }
+
+    /// The number of scripted events the next `libdecor_dispatch` will deliver.
+    pub fn pending(&self) -> usize {
+        self.data().pending.borrow().len()
+    }
+
+    /// The timeout passed to the last `libdecor_dispatch`, if any.
+    pub fn last_timeout(&self) -> Option<c_int> {
+        self.data().last_timeout.get()
+    }
+
+    /// Queues a call to `libdecor_interface::error`.
+    pub fn error(&self, error: libdecor_error, message: &CStr) {
+        self.data().push(Pending::Error(error, message.into()));
+    }
+
+    /// Calls `libdecor_interface::error` right away, the way libdecor does when the application
+    /// dispatches the `wl_display` itself.
+    pub fn raise_error(&self, error: libdecor_error, message: &CStr) {
+        let iface = unsafe { &*self.data().iface };
+        if let Some(on_error) = iface.error {
+            unsafe { on_error(self.0, error, message.as_ptr()) };
+        }
+    }
+
+    fn data(&self) -> &ContextData {
+        assert!(
+            LIVE.with_borrow(|live| live.contexts.contains(&self.0.cast())),
+            "mock libdecor context was already unreferenced"
+        );
+        unsafe { &*self.0.cast::<ContextData>() }
+    }
+}
+
+/// A handle to a mocked `libdecor_frame`.
+///
+/// Methods panic if the frame has already been unreferenced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MockFrame(*mut libdecor_frame);
+
+impl MockFrame {
+    pub fn as_raw(&self) -> *mut libdecor_frame {
+        self.0
+    }
+
+    pub fn context(&self) -> MockContext {
+        MockContext(self.data().context.cast())
+    }
+
+    /// The surface passed to `libdecor_decorate`.
+    pub fn surface(&self) -> *mut wl_surface {
+        self.data().surface
+    }
+
+    /// Makes `libdecor_frame_get_xdg_surface` and `libdecor_frame_get_xdg_toplevel` return these
+    /// instead of placeholders once the context has been dispatched.
+    pub fn set_shell_objects(
+        &self,
+        xdg_surface: *mut xdg_surface,
+        xdg_toplevel: *mut xdg_toplevel,
+    ) {
+        self.data().properties.borrow_mut().shell_objects = Some((xdg_surface, xdg_toplevel));
+    }
+
+    /// Queues a call to `libdecor_frame_interface::configure` with a configuration reporting the
+    /// given content size and window state.
+    pub fn configure(
+        &self,
+        content_size: Option<(c_int, c_int)>,
+        window_state: Option<libdecor_window_state>,
+    ) {
+        let configuration = ConfigurationData {
+            content_size,
+            window_state,
+        };
+        self.push(Pending::Configure(self.0.cast(), configuration));
+    }
+
+    /// Queues a call to `libdecor_frame_interface::close`.
+    pub fn close(&self) {
+        self.push(Pending::Close(self.0.cast()));
+    }
+
+    /// Queues a call to `libdecor_frame_interface::commit`.
+    pub fn commit(&self) {
+        self.push(Pending::Commit(self.0.cast()));
+    }
+
+    /// Queues a call to `libdecor_frame_interface::dismiss_popup`.
+    pub fn dismiss_popup(&self, seat: &CStr) {
+        self.push(Pending::DismissPopup(self.0.cast(), seat.into()));
+    }
+
+    /// The requests made on this frame so far.
+    pub fn calls(&self) -> Vec<Call> {
+        self.data().calls.borrow().clone()
+    }
+
+    /// Returns and forgets the requests made on this frame so far.
+    pub fn take_calls(&self) -> Vec<Call> {
+        self.data().calls.take()
+    }
+
+    pub fn title(&self) -> Option<CString> {
+        self.data().properties.borrow().title.clone()
+    }
+
+    pub fn app_id(&self) -> Option<CString> {
+        self.data().properties.borrow().app_id.clone()
+    }
+
+    pub fn capabilities(&self) -> libdecor_capabilities {
+        self.data().properties.borrow().capabilities
+    }
+
+    pub fn parent(&self) -> *mut libdecor_frame {
+        self.data().properties.borrow().parent
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.data().properties.borrow().visible
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        self.data().properties.borrow().mapped
+    }
+
+    pub fn min_content_size(&self) -> (c_int, c_int) {
+        self.data().properties.borrow().min_content_size
+    }
+
+    pub fn max_content_size(&self) -> (c_int, c_int) {
+        self.data().properties.borrow().max_content_size
+    }
+
+    /// The content size of the last commit.
+    pub fn content_size(&self) -> (c_int, c_int) {
+        self.data().properties.borrow().content_size
+    }
+
+    /// The window state of the last configuration committed.
+    pub fn window_state(&self) -> libdecor_window_state {
+        self.data().properties.borrow().window_state
+    }
+
+    fn push(&self, event: Pending) {
+        self.context().data().push(event);
+    }
+
+    fn data(&self) -> &FrameData {
+        assert!(
+            LIVE.with_borrow(|live| live.frames.contains(&self.0.cast())),
+            "mock libdecor frame was already unreferenced"
+        );
+        unsafe { &*self.0.cast::<FrameData>() }
+    }
+}
+
+thread_local! {
+    static LIVE: RefCell<Live> = const {
+        RefCell::new(Live {
+            contexts: Vec::new(),
+            frames: Vec::new(),
+        })
+    };
+}
+
+struct Live {
+    contexts: Vec<*mut ContextData>,
+    frames: Vec<*mut FrameData>,
+}
+
+/// What a `*mut libdecor` points to.
+struct ContextData {
+    display: *mut wl_display,
+    iface: *mut libdecor_interface,
+    initialized: Cell<bool>,
+    last_timeout: Cell<Option<c_int>>,
+    pending: RefCell<VecDeque<Pending>>,
+    // `reader` is readable whenever `pending` isn't empty.
+    reader: UnixStream,
+    writer: UnixStream,
+}
+
+enum Pending {
+    Error(libdecor_error, CString),
+    Configure(*mut FrameData, ConfigurationData),
+    Close(*mut FrameData),
+    Commit(*mut FrameData),
+    DismissPopup(*mut FrameData, CString),
+}
+
+impl Pending {
+    fn frame(&self) -> *mut FrameData {
+        match *self {
+            Self::Error(..) => null_mut(),
+            Self::Configure(frame, _)
+            | Self::Close(frame)
+            | Self::Commit(frame)
+            | Self::DismissPopup(frame, _) => frame,
+        }
+    }
+}
+
+impl ContextData {
+    fn new(display: *mut wl_display, iface: *mut libdecor_interface) -> Self {
+        let (reader, writer) = UnixStream::pair().expect("failed to create a socket pair");
+        reader.set_nonblocking(true).unwrap();
+        writer.set_nonblocking(true).unwrap();
+        Self {
+            display,
+            iface,
+            initialized: Cell::new(false),
+            last_timeout: Cell::new(None),
+            pending: RefCell::new(VecDeque::new()),
+            reader,
+            writer,
+        }
+    }
+
+    fn fd(&self) -> c_int {
+        self.reader.as_raw_fd()
+    }
+
+    fn push(&self, event: Pending) {
+        self.pending.borrow_mut().push_back(event);
+        self.signal();
+    }
+
+    fn signal(&self) {
+        match (&self.writer).write(&[0]) {
+            Ok(_) => {}
+            // The fd is already readable.
+            Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+            Err(err) => panic!("failed to signal the mock libdecor fd: {err}"),
+        }
+    }
+
+    fn drain(&self) {
+        let mut buf = [0; 64];
+        while let Ok(1..) = (&self.reader).read(&mut buf) {}
+    }
+}
+
+/// What a `*mut libdecor_frame` points to.
+struct FrameData {
+    context: *mut ContextData,
+    surface: *mut wl_surface,
+    iface: *mut libdecor_frame_interface,
+    user_data: *mut c_void,
+    refs: Cell<usize>,
+    properties: RefCell<Properties>,
+    calls: RefCell<Vec<Call>>,
+}
+
+struct Properties {
+    title: Option<CString>,
+    app_id: Option<CString>,
+    capabilities: libdecor_capabilities,
+    parent: *mut libdecor_frame,
+    visible: bool,
+    mapped: bool,
+    min_content_size: (c_int, c_int),
+    max_content_size: (c_int, c_int),
+    content_size: (c_int, c_int),
+    window_state: libdecor_window_state,
+    shell_objects: Option<(*mut xdg_surface, *mut xdg_toplevel)>,
+}
+
+impl Default for Properties {
+    fn default() -> Self {
+        Self {
+            title: None,
+            app_id: None,
+            capabilities: LIBDECOR_ACTION_MOVE
+                | LIBDECOR_ACTION_RESIZE
+                | LIBDECOR_ACTION_MINIMIZE
+                | LIBDECOR_ACTION_FULLSCREEN
+                | LIBDECOR_ACTION_CLOSE,
+            parent: null_mut(),
+            visible: true,
+            mapped: false,
+            min_content_size: (0, 0),
+            max_content_size: (0, 0),
+            content_size: (0, 0),
+            window_state: LIBDECOR_WINDOW_STATE_NONE,
+            shell_objects: None,
+        }
+    }
+}
+
+impl FrameData {
+    fn record(&self, call: Call) {
+        self.calls.borrow_mut().push(call);
+    }
+}
+
+/// What a `*mut libdecor_configuration` points to.
+struct ConfigurationData {
+    content_size: Option<(c_int, c_int)>,
+    window_state: Option<libdecor_window_state>,
+}
+
+/// What a `*mut libdecor_state` points to.
+struct StateData {
+    width: c_int,
+    height: c_int,
+}
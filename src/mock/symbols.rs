@@ -0,0 +1,493 @@
+use core::{
+    cell::{Cell, RefCell},
+    ffi::{CStr, c_char, c_int, c_void},
+    ptr::{null, null_mut},
+};
+use std::{boxed::Box, vec::Vec};
+
+use wayland_headers::{wayland_client::*, xdg_shell_client_protocol::*};
+
+use super::{
+    Call, ConfigurationData, ContextData, FrameData, LIVE, Pending, Properties, StateData,
+};
+use crate::libdecor::*;
+
+unsafe fn context_data<'a>(context: *mut libdecor) -> &'a ContextData {
+    unsafe { &*context.cast::<ContextData>() }
+}
+
+unsafe fn frame_data<'a>(frame: *const libdecor_frame) -> &'a FrameData {
+    unsafe { &*frame.cast::<FrameData>() }
+}
+
+/// The frame's `xdg_surface` and `xdg_toplevel`, which only exist once the context has been
+/// dispatched. The frame itself stands in for them unless the test provided real ones.
+unsafe fn shell_objects(frame: *mut libdecor_frame) -> (*mut xdg_surface, *mut xdg_toplevel) {
+    let data = unsafe { frame_data(frame) };
+    match unsafe { (*data.context).initialized.get() } {
+        true => (data.properties.borrow().shell_objects).unwrap_or((frame.cast(), frame.cast())),
+        false => (null_mut(), null_mut()),
+    }
+}
+
+/// Calls the frame's `iface` callback for a scripted event, holding a reference to the frame.
+unsafe fn deliver(context: *mut libdecor, event: Pending) {
+    let frame = event.frame().cast::<libdecor_frame>();
+    if !frame.is_null() {
+        unsafe { libdecor_frame_ref(frame) };
+    }
+    match event {
+        Pending::Error(error, message) => {
+            let iface = unsafe { &*context_data(context).iface };
+            if let Some(on_error) = iface.error {
+                unsafe { on_error(context, error, message.as_ptr()) };
+            }
+        }
+        Pending::Configure(_, mut configuration) => {
+            let data = unsafe { frame_data(frame) };
+            if let Some(configure) = unsafe { (*data.iface).configure } {
+                let configuration = (&raw mut configuration).cast();
+                unsafe { configure(frame, configuration, data.user_data) };
+            }
+        }
+        Pending::Close(_) => {
+            let data = unsafe { frame_data(frame) };
+            if let Some(close) = unsafe { (*data.iface).close } {
+                unsafe { close(frame, data.user_data) };
+            }
+        }
+        Pending::Commit(_) => {
+            let data = unsafe { frame_data(frame) };
+            if let Some(commit) = unsafe { (*data.iface).commit } {
+                unsafe { commit(frame, data.user_data) };
+            }
+        }
+        Pending::DismissPopup(_, seat) => {
+            let data = unsafe { frame_data(frame) };
+            if let Some(dismiss_popup) = unsafe { (*data.iface).dismiss_popup } {
+                unsafe { dismiss_popup(frame, seat.as_ptr(), data.user_data) };
+            }
+        }
+    }
+    if !frame.is_null() {
+        unsafe { libdecor_frame_unref(frame) };
+    }
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_configuration_get_content_size(
+    configuration: *mut libdecor_configuration,
+    _frame: *mut libdecor_frame,
+    width: *mut c_int,
+    height: *mut c_int,
+) -> bool {
+    let configuration = unsafe { &*configuration.cast::<ConfigurationData>() };
+    match configuration.content_size {
+        Some((w, h)) if w > 0 && h > 0 => {
+            unsafe { (width.write(w), height.write(h)) };
+            true
+        }
+        _ => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_configuration_get_window_state(
+    configuration: *mut libdecor_configuration,
+    window_state: *mut libdecor_window_state,
+) -> bool {
+    let configuration = unsafe { &*configuration.cast::<ConfigurationData>() };
+    match configuration.window_state {
+        Some(state) => {
+            unsafe { window_state.write(state) };
+            true
+        }
+        None => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_decorate(
+    context: *mut libdecor,
+    surface: *mut wl_surface,
+    iface: *mut libdecor_frame_interface,
+    user_data: *mut c_void,
+) -> *mut libdecor_frame {
+    let data = Box::into_raw(Box::new(FrameData {
+        context: context.cast(),
+        surface,
+        iface,
+        user_data,
+        refs: Cell::new(1),
+        properties: RefCell::new(Properties::default()),
+        calls: RefCell::new(Vec::new()),
+    }));
+    LIVE.with_borrow_mut(|live| live.frames.push(data));
+    data.cast()
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_dispatch(context: *mut libdecor, timeout: c_int) -> c_int {
+    let data = unsafe { context_data(context) };
+    data.initialized.set(true);
+    data.last_timeout.set(Some(timeout));
+    data.drain();
+
+    // Events scripted by the callbacks themselves wait for the next dispatch.
+    let scripted = data.pending.borrow().len();
+    let mut dispatched = 0;
+    for _ in 0..scripted {
+        let Some(event) = data.pending.borrow_mut().pop_front() else {
+            break;
+        };
+        unsafe { deliver(context, event) };
+        dispatched += 1;
+    }
+
+    if !data.pending.borrow().is_empty() {
+        data.signal();
+    }
+    dispatched
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_close(frame: *mut libdecor_frame) {
+    unsafe { frame_data(frame) }.record(Call::Close);
+    let context = unsafe { frame_data(frame) }.context;
+    unsafe { deliver(context.cast(), Pending::Close(frame.cast())) };
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_commit(
+    frame: *mut libdecor_frame,
+    state: *mut libdecor_state,
+    configuration: *mut libdecor_configuration,
+) {
+    let data = unsafe { frame_data(frame) };
+    let state = unsafe { &*state.cast::<StateData>() };
+    let mut properties = data.properties.borrow_mut();
+    properties.content_size = (state.width, state.height);
+    if !configuration.is_null() {
+        let configuration = unsafe { &*configuration.cast::<ConfigurationData>() };
+        if let Some(window_state) = configuration.window_state {
+            properties.window_state = window_state;
+        }
+    }
+    data.record(Call::Commit {
+        width: state.width,
+        height: state.height,
+        configured: !configuration.is_null(),
+    });
+}
+
+#[cfg(feature = "libdecor-0-1-1")]
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_get_max_content_size(
+    frame: *const libdecor_frame,
+    content_width: *mut c_int,
+    content_height: *mut c_int,
+) {
+    let (width, height) = unsafe { frame_data(frame) }
+        .properties
+        .borrow()
+        .max_content_size;
+    unsafe { (content_width.write(width), content_height.write(height)) };
+}
+
+#[cfg(feature = "libdecor-0-1-1")]
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_get_min_content_size(
+    frame: *const libdecor_frame,
+    content_width: *mut c_int,
+    content_height: *mut c_int,
+) {
+    let (width, height) = unsafe { frame_data(frame) }
+        .properties
+        .borrow()
+        .min_content_size;
+    unsafe { (content_width.write(width), content_height.write(height)) };
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_get_title(frame: *mut libdecor_frame) -> *const c_char {
+    let properties = unsafe { frame_data(frame) }.properties.borrow();
+    properties.title.as_deref().map_or(null(), CStr::as_ptr)
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_get_xdg_surface(
+    frame: *mut libdecor_frame,
+) -> *mut xdg_surface {
+    unsafe { shell_objects(frame) }.0
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_get_xdg_toplevel(
+    frame: *mut libdecor_frame,
+) -> *mut xdg_toplevel {
+    unsafe { shell_objects(frame) }.1
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_has_capability(
+    frame: *mut libdecor_frame,
+    capability: libdecor_capabilities,
+) -> bool {
+    let capabilities = unsafe { frame_data(frame) }
+        .properties
+        .borrow()
+        .capabilities;
+    capabilities & capability != 0
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_is_floating(frame: *mut libdecor_frame) -> bool {
+    let window_state = unsafe { frame_data(frame) }
+        .properties
+        .borrow()
+        .window_state;
+    window_state
+        & (LIBDECOR_WINDOW_STATE_MAXIMIZED
+            | LIBDECOR_WINDOW_STATE_FULLSCREEN
+            | LIBDECOR_WINDOW_STATE_TILED_LEFT
+            | LIBDECOR_WINDOW_STATE_TILED_RIGHT
+            | LIBDECOR_WINDOW_STATE_TILED_TOP
+            | LIBDECOR_WINDOW_STATE_TILED_BOTTOM)
+        == 0
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_is_visible(frame: *mut libdecor_frame) -> bool {
+    unsafe { frame_data(frame) }.properties.borrow().visible
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_map(frame: *mut libdecor_frame) {
+    let data = unsafe { frame_data(frame) };
+    data.properties.borrow_mut().mapped = true;
+    data.record(Call::Map);
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_move(
+    frame: *mut libdecor_frame,
+    _wl_seat: *mut wl_seat,
+    serial: u32,
+) {
+    unsafe { frame_data(frame) }.record(Call::Move { serial });
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_popup_grab(
+    frame: *mut libdecor_frame,
+    seat_name: *const c_char,
+) {
+    let seat = unsafe { CStr::from_ptr(seat_name) }.into();
+    unsafe { frame_data(frame) }.record(Call::PopupGrab { seat });
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_popup_ungrab(
+    frame: *mut libdecor_frame,
+    seat_name: *const c_char,
+) {
+    let seat = unsafe { CStr::from_ptr(seat_name) }.into();
+    unsafe { frame_data(frame) }.record(Call::PopupUngrab { seat });
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_ref(frame: *mut libdecor_frame) {
+    let refs = &unsafe { frame_data(frame) }.refs;
+    refs.set(refs.get() + 1);
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_resize(
+    frame: *mut libdecor_frame,
+    _wl_seat: *mut wl_seat,
+    serial: u32,
+    edge: libdecor_resize_edge,
+) {
+    unsafe { frame_data(frame) }.record(Call::Resize { serial, edge });
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_app_id(frame: *mut libdecor_frame, app_id: *const c_char) {
+    let data = unsafe { frame_data(frame) };
+    let app_id = unsafe { CStr::from_ptr(app_id) };
+    data.properties.borrow_mut().app_id = Some(app_id.into());
+    data.record(Call::SetAppId(app_id.into()));
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_capabilities(
+    frame: *mut libdecor_frame,
+    capabilities: libdecor_capabilities,
+) {
+    let data = unsafe { frame_data(frame) };
+    data.properties.borrow_mut().capabilities |= capabilities;
+    data.record(Call::SetCapabilities(capabilities));
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_fullscreen(
+    frame: *mut libdecor_frame,
+    output: *mut wl_output,
+) {
+    unsafe { frame_data(frame) }.record(Call::SetFullscreen { output });
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_max_content_size(
+    frame: *mut libdecor_frame,
+    content_width: c_int,
+    content_height: c_int,
+) {
+    let data = unsafe { frame_data(frame) };
+    data.properties.borrow_mut().max_content_size = (content_width, content_height);
+    data.record(Call::SetMaxContentSize(content_width, content_height));
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_maximized(frame: *mut libdecor_frame) {
+    unsafe { frame_data(frame) }.record(Call::SetMaximized);
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_min_content_size(
+    frame: *mut libdecor_frame,
+    content_width: c_int,
+    content_height: c_int,
+) {
+    let data = unsafe { frame_data(frame) };
+    data.properties.borrow_mut().min_content_size = (content_width, content_height);
+    data.record(Call::SetMinContentSize(content_width, content_height));
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_minimized(frame: *mut libdecor_frame) {
+    unsafe { frame_data(frame) }.record(Call::SetMinimized);
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_parent(
+    frame: *mut libdecor_frame,
+    parent: *mut libdecor_frame,
+) {
+    let data = unsafe { frame_data(frame) };
+    data.properties.borrow_mut().parent = parent;
+    data.record(Call::SetParent(parent));
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_title(frame: *mut libdecor_frame, title: *const c_char) {
+    let data = unsafe { frame_data(frame) };
+    let title = unsafe { CStr::from_ptr(title) };
+    data.properties.borrow_mut().title = Some(title.into());
+    data.record(Call::SetTitle(title.into()));
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_set_visibility(frame: *mut libdecor_frame, visible: bool) {
+    let data = unsafe { frame_data(frame) };
+    data.properties.borrow_mut().visible = visible;
+    data.record(Call::SetVisibility(visible));
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_show_window_menu(
+    frame: *mut libdecor_frame,
+    _wl_seat: *mut wl_seat,
+    serial: u32,
+    x: c_int,
+    y: c_int,
+) {
+    unsafe { frame_data(frame) }.record(Call::ShowWindowMenu { serial, x, y });
+}
+
+// There are no decorations, so frame and surface coordinates are the same.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_translate_coordinate(
+    _frame: *mut libdecor_frame,
+    surface_x: c_int,
+    surface_y: c_int,
+    frame_x: *mut c_int,
+    frame_y: *mut c_int,
+) {
+    unsafe { (frame_x.write(surface_x), frame_y.write(surface_y)) };
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_unref(frame: *mut libdecor_frame) {
+    let data = unsafe { frame_data(frame) };
+    let refs = data.refs.get() - 1;
+    data.refs.set(refs);
+    if refs > 0 {
+        return;
+    }
+
+    let frame = frame.cast::<FrameData>();
+    LIVE.with_borrow_mut(|live| {
+        live.frames.retain(|&live| live != frame);
+        if live.contexts.contains(&data.context) {
+            let context = unsafe { &*data.context };
+            context
+                .pending
+                .borrow_mut()
+                .retain(|event| event.frame() != frame);
+        }
+    });
+    drop(unsafe { Box::from_raw(frame) });
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_unset_capabilities(
+    frame: *mut libdecor_frame,
+    capabilities: libdecor_capabilities,
+) {
+    let data = unsafe { frame_data(frame) };
+    data.properties.borrow_mut().capabilities &= !capabilities;
+    data.record(Call::UnsetCapabilities(capabilities));
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_unset_fullscreen(frame: *mut libdecor_frame) {
+    unsafe { frame_data(frame) }.record(Call::UnsetFullscreen);
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_frame_unset_maximized(frame: *mut libdecor_frame) {
+    unsafe { frame_data(frame) }.record(Call::UnsetMaximized);
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_get_fd(context: *mut libdecor) -> c_int {
+    unsafe { context_data(context) }.fd()
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_new(
+    display: *mut wl_display,
+    iface: *mut libdecor_interface,
+) -> *mut libdecor {
+    let data = Box::into_raw(Box::new(ContextData::new(display, iface)));
+    LIVE.with_borrow_mut(|live| live.contexts.push(data));
+    data.cast()
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_state_free(state: *mut libdecor_state) {
+    drop(unsafe { Box::from_raw(state.cast::<StateData>()) });
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_state_new(width: c_int, height: c_int) -> *mut libdecor_state {
+    Box::into_raw(Box::new(StateData { width, height })).cast()
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn libdecor_unref(context: *mut libdecor) {
+    let context = context.cast::<ContextData>();
+    LIVE.with_borrow_mut(|live| live.contexts.retain(|&live| live != context));
+    drop(unsafe { Box::from_raw(context) });
+}
@@ -0,0 +1,103 @@
+use std::{
+    ffi::{CStr, c_char, c_void},
+    ptr::null_mut,
+    vec,
+};
+
+use super::{Call, contexts, frames};
+use crate::libdecor::*;
+
+unsafe extern "C" fn on_error(
+    _context: *mut libdecor,
+    error: libdecor_error,
+    message: *const c_char,
+) {
+    assert_eq!(error, LIBDECOR_ERROR_INVALID_FRAME_CONFIGURATION);
+    assert_eq!(unsafe { CStr::from_ptr(message) }, c"oops");
+}
+
+unsafe extern "C" fn on_configure(
+    frame: *mut libdecor_frame,
+    configuration: *mut libdecor_configuration,
+    _user_data: *mut c_void,
+) {
+    let (mut width, mut height) = (640, 480);
+    unsafe {
+        libdecor_configuration_get_content_size(configuration, frame, &mut width, &mut height)
+    };
+    let state = unsafe { libdecor_state_new(width, height) };
+    unsafe { libdecor_frame_commit(frame, state, configuration) };
+    unsafe { libdecor_state_free(state) };
+}
+
+unsafe extern "C" fn on_close(frame: *mut libdecor_frame, user_data: *mut c_void) {
+    unsafe { libdecor_frame_unref(frame) };
+    unsafe { *user_data.cast::<bool>() = true };
+}
+
+#[test]
+fn scripted_session() {
+    let mut iface = libdecor_interface {
+        error: Some(on_error),
+        ..unsafe { core::mem::zeroed() }
+    };
+    let mut frame_iface = libdecor_frame_interface {
+        configure: Some(on_configure),
+        close: Some(on_close),
+        ..unsafe { core::mem::zeroed() }
+    };
+    let mut closed = false;
+
+    let context = unsafe { libdecor_new(null_mut(), &mut iface) };
+    let frame = unsafe {
+        libdecor_decorate(
+            context,
+            null_mut(),
+            &mut frame_iface,
+            (&raw mut closed).cast(),
+        )
+    };
+    unsafe { libdecor_frame_set_title(frame, c"mock".as_ptr()) };
+    unsafe { libdecor_frame_map(frame) };
+
+    let [mock_context] = contexts()[..] else {
+        panic!()
+    };
+    let [mock_frame] = frames()[..] else { panic!() };
+    assert_eq!(mock_frame.context(), mock_context);
+    assert_eq!(mock_frame.title().as_deref(), Some(c"mock"));
+
+    mock_frame.configure(None, Some(LIBDECOR_WINDOW_STATE_ACTIVE));
+    mock_frame.configure(Some((800, 600)), Some(LIBDECOR_WINDOW_STATE_MAXIMIZED));
+    mock_context.error(LIBDECOR_ERROR_INVALID_FRAME_CONFIGURATION, c"oops");
+    assert_eq!(unsafe { libdecor_dispatch(context, -1) }, 3);
+    assert_eq!(unsafe { libdecor_dispatch(context, -1) }, 0);
+
+    assert_eq!(mock_frame.content_size(), (800, 600));
+    assert!(!unsafe { libdecor_frame_is_floating(frame) });
+    assert_eq!(
+        mock_frame.take_calls(),
+        vec![
+            Call::SetTitle(c"mock".into()),
+            Call::Map,
+            Call::Commit {
+                width: 640,
+                height: 480,
+                configured: true,
+            },
+            Call::Commit {
+                width: 800,
+                height: 600,
+                configured: true,
+            },
+        ],
+    );
+
+    mock_frame.close();
+    assert_eq!(unsafe { libdecor_dispatch(context, 0) }, 1);
+    assert!(closed);
+    assert!(frames().is_empty());
+
+    unsafe { libdecor_unref(context) };
+    assert!(contexts().is_empty());
+}
@@ -0,0 +1,120 @@
+//! Runtime detection of which libdecor release is loaded.
+//!
+//! libdecor has no version function, so [`probe`] infers a lower bound from the symbols it exports
+//! and, where the library is installed under its full file name, from that file name.
+
+use core::{
+    ffi::{CStr, c_void},
+    fmt,
+    mem::MaybeUninit,
+};
+use std::{fs, path::PathBuf};
+
+use libc::{RTLD_DEFAULT, dladdr, dlsym};
+
+#[cfg(test)]
+mod test;
+
+/// A libdecor release.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub micro: u32,
+}
+
+/// What [`probe`] found out about the loaded libdecor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LibdecorInfo {
+    /// The oldest release consistent with what was found.
+    pub min_version: Version,
+    /// Whether `LIBDECOR_WINDOW_STATE_SUSPENDED` can be reported, which requires 0.2.0.
+    pub has_suspended_state: bool,
+    /// Whether `libdecor_frame_get_min_content_size` and `libdecor_frame_get_max_content_size`
+    /// are exported, which requires 0.1.1.
+    pub has_min_max_getters: bool,
+    /// The file libdecor was loaded from, with symbolic links resolved where possible.
+    pub library_path: Option<PathBuf>,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, micro: u32) -> Self {
+        Self {
+            major,
+            minor,
+            micro,
+        }
+    }
+
+    /// Parses a `libdecor-0.so.0.<minor * 100>.<micro>` file name, e.g. `libdecor-0.so.0.200.2`
+    /// for 0.2.2.
+    fn from_file_name(file_name: &str) -> Option<Self> {
+        let (minor, micro) = file_name
+            .strip_prefix("libdecor-0.so.0.")?
+            .split_once('.')?;
+        let minor: u32 = minor.parse().ok()?;
+        let micro: u32 = micro.parse().ok()?;
+        Some(Self::new(0, minor / 100, micro))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.micro)
+    }
+}
+
+/// Probes the libdecor linked into, or otherwise globally loaded by, the current process.
+///
+/// Returns `None` if `libdecor_new` can't be found.
+pub fn probe() -> Option<LibdecorInfo> {
+    unsafe { probe_handle(RTLD_DEFAULT) }
+}
+
+/// Probes the library behind a `dlopen` handle.
+pub(crate) unsafe fn probe_handle(handle: *mut c_void) -> Option<LibdecorInfo> {
+    let has_symbol = |symbol: &CStr| !unsafe { dlsym(handle, symbol.as_ptr()) }.is_null();
+
+    let libdecor_new = unsafe { dlsym(handle, c"libdecor_new".as_ptr()) };
+    if libdecor_new.is_null() {
+        return None;
+    }
+
+    let has_min_max_getters = has_symbol(c"libdecor_frame_get_min_content_size")
+        && has_symbol(c"libdecor_frame_get_max_content_size");
+    let mut min_version = match has_min_max_getters {
+        true => Version::new(0, 1, 1),
+        false => Version::new(0, 1, 0),
+    };
+
+    let library_path = unsafe { library_path(libdecor_new) };
+    let file_version = library_path
+        .as_ref()
+        .and_then(|path| path.file_name()?.to_str())
+        .and_then(Version::from_file_name);
+    if let Some(file_version) = file_version {
+        min_version = min_version.max(file_version);
+    }
+
+    Some(LibdecorInfo {
+        min_version,
+        has_suspended_state: min_version >= Version::new(0, 2, 0),
+        has_min_max_getters,
+        library_path,
+    })
+}
+
+unsafe fn library_path(symbol: *mut c_void) -> Option<PathBuf> {
+    let mut info = MaybeUninit::uninit();
+    if unsafe { dladdr(symbol, info.as_mut_ptr()) } == 0 {
+        return None;
+    }
+    let info = unsafe { info.assume_init() };
+    if info.dli_fname.is_null() {
+        return None;
+    }
+
+    let file_name = unsafe { CStr::from_ptr(info.dli_fname) };
+    let path = PathBuf::from(file_name.to_str().ok()?);
+    Some(fs::canonicalize(&path).unwrap_or(path))
+}
@@ -0,0 +1,23 @@
+use super::Version;
+
+#[test]
+fn version_from_file_name() {
+    let version = Version::from_file_name;
+    assert_eq!(
+        version("libdecor-0.so.0.100.1"),
+        Some(Version::new(0, 1, 1))
+    );
+    assert_eq!(
+        version("libdecor-0.so.0.200.0"),
+        Some(Version::new(0, 2, 0))
+    );
+    assert_eq!(
+        version("libdecor-0.so.0.200.2"),
+        Some(Version::new(0, 2, 2))
+    );
+    assert_eq!(version("libdecor-0.so.0"), None);
+    assert_eq!(version("libdecor-0.so"), None);
+    assert_eq!(version("libdecor-0.so.0.200"), None);
+    assert_eq!(version("libdecor-0.so.0.200.2.1"), None);
+    assert_eq!(version("libfoo.so.0.200.2"), None);
+}
@@ -0,0 +1,48 @@
+//! Safe, idiomatic wrappers around [`crate::libdecor`].
+//!
+//! Unlike the rest of this crate, this module requires `std` and is only available with the `safe`
+//! feature.
+
+/// Emits a `tracing` event at `$level` when the `tracing` feature is enabled.
+macro_rules! emit {
+    ($level:ident, $name:literal $(, $($field:tt)+)?) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!($($($field)+,)? $name);
+    };
+}
+
+#[cfg(feature = "calloop")]
+mod calloop;
+mod configuration;
+mod context;
+mod error;
+mod event;
+mod flags;
+mod frame;
+mod handler;
+#[cfg(feature = "raw-window-handle")]
+mod raw_window_handle;
+mod resize_edge;
+mod state;
+#[cfg(test)]
+mod test;
+#[cfg(feature = "tokio")]
+mod tokio;
+#[cfg(feature = "wayland-client")]
+mod wayland_client;
+mod window_builder;
+
+#[cfg(feature = "calloop")]
+pub use calloop::*;
+pub use configuration::*;
+pub use context::*;
+pub use error::*;
+pub use event::*;
+pub use flags::*;
+pub use frame::*;
+pub use handler::*;
+pub use resize_edge::*;
+pub use state::*;
+#[cfg(feature = "tokio")]
+pub use tokio::*;
+pub use window_builder::*;
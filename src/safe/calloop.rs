@@ -0,0 +1,109 @@
+use core::time::Duration;
+use std::{io, rc::Rc};
+
+use ::calloop::{EventSource, Interest, Mode, Poll, PostAction, Readiness, Token, TokenFactory};
+
+use super::{Context, FrameEvent, FrameEventQueue, FrameId};
+
+#[cfg(all(test, feature = "mock"))]
+mod test;
+
+/// A calloop [`EventSource`] that dispatches a [`Context`] whenever its fd is readable and
+/// delivers the events pushed onto its [`FrameEventQueue`].
+pub struct LibdecorSource {
+    context: Rc<Context>,
+    queue: FrameEventQueue,
+    token: Option<Token>,
+}
+
+impl LibdecorSource {
+    pub fn new(context: Context) -> Self {
+        Self {
+            context: Rc::new(context),
+            queue: FrameEventQueue::new(),
+            token: None,
+        }
+    }
+
+    /// Returns the context, which frames can borrow while the source is inserted in a loop.
+    pub fn context(&self) -> Rc<Context> {
+        self.context.clone()
+    }
+
+    /// Returns the queue whose handlers deliver their events through this source.
+    pub fn queue(&self) -> FrameEventQueue {
+        self.queue.clone()
+    }
+
+    fn dispatch(&self) -> io::Result<bool> {
+        self.context.dispatch(Some(Duration::ZERO))?;
+        Ok(!self.queue.is_empty())
+    }
+}
+
+impl EventSource for LibdecorSource {
+    type Event = (FrameId, FrameEvent);
+    type Metadata = ();
+    type Ret = ();
+    type Error = io::Error;
+
+    // Requests made by callbacks must be flushed before the loop sleeps.
+    const NEEDS_EXTRA_LIFECYCLE_EVENTS: bool = true;
+
+    fn process_events<F>(
+        &mut self,
+        _readiness: Readiness,
+        token: Token,
+        mut callback: F,
+    ) -> io::Result<PostAction>
+    where
+        F: FnMut(Self::Event, &mut Self::Metadata) -> Self::Ret,
+    {
+        if self.token != Some(token) {
+            return Ok(PostAction::Continue);
+        }
+
+        self.dispatch()?;
+        while let Some(event) = self.queue.pop() {
+            callback(event, &mut ());
+        }
+        Ok(PostAction::Continue)
+    }
+
+    fn register(
+        &mut self,
+        poll: &mut Poll,
+        token_factory: &mut TokenFactory,
+    ) -> ::calloop::Result<()> {
+        let token = token_factory.token();
+        // The fd belongs to the context's `wl_display`, which outlives it, and the context lives
+        // as long as this source.
+        unsafe { poll.register(&*self.context, Interest::READ, Mode::Level, token)? };
+        self.token = Some(token);
+        Ok(())
+    }
+
+    fn reregister(
+        &mut self,
+        poll: &mut Poll,
+        token_factory: &mut TokenFactory,
+    ) -> ::calloop::Result<()> {
+        let token = token_factory.token();
+        poll.reregister(&*self.context, Interest::READ, Mode::Level, token)?;
+        self.token = Some(token);
+        Ok(())
+    }
+
+    fn unregister(&mut self, poll: &mut Poll) -> ::calloop::Result<()> {
+        poll.unregister(&*self.context)?;
+        self.token = None;
+        Ok(())
+    }
+
+    fn before_sleep(&mut self) -> ::calloop::Result<Option<(Readiness, Token)>> {
+        match (self.dispatch()?, self.token) {
+            (true, Some(token)) => Ok(Some((Readiness::EMPTY, token))),
+            _ => Ok(None),
+        }
+    }
+}
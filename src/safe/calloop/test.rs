@@ -0,0 +1,55 @@
+use std::{ptr::null_mut, time::Instant, vec, vec::Vec};
+
+use ::calloop::EventLoop;
+
+use super::*;
+use crate::mock::frames;
+
+#[test]
+fn source_delivers_frame_events_and_drains_the_fd() {
+    let source = LibdecorSource::new(unsafe { Context::new(null_mut(), |_, _| {}) }.unwrap());
+    let context = source.context();
+    let queue = source.queue();
+    let first = unsafe { context.decorate(null_mut(), queue.handler(640, 480)) }.unwrap();
+    let second = unsafe { context.decorate(null_mut(), queue.handler(640, 480)) }.unwrap();
+    let [first_mock, second_mock] = frames()[..] else {
+        panic!()
+    };
+
+    let mut event_loop = EventLoop::<Vec<(FrameId, FrameEvent)>>::try_new().unwrap();
+    event_loop
+        .handle()
+        .insert_source(source, |event, (), events| events.push(event))
+        .unwrap();
+
+    second_mock.close();
+    first_mock.configure(Some((800, 600)), None);
+    first_mock.commit();
+    let mut events = Vec::new();
+    event_loop
+        .dispatch(Some(Duration::ZERO), &mut events)
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            (second.id(), FrameEvent::Close),
+            (
+                first.id(),
+                FrameEvent::Configure {
+                    size: Some((800, 600)),
+                    state: None,
+                    committed: Ok((800, 600)),
+                },
+            ),
+            (first.id(), FrameEvent::Commit),
+        ],
+    );
+
+    // A readable fd would wake the loop right away.
+    events.clear();
+    let timeout = Duration::from_millis(50);
+    let start = Instant::now();
+    event_loop.dispatch(Some(timeout), &mut events).unwrap();
+    assert!(start.elapsed() >= timeout);
+    assert!(events.is_empty());
+}
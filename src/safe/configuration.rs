@@ -0,0 +1,66 @@
+use core::{ffi::c_int, marker::PhantomData, ptr::NonNull};
+
+use super::{FrameRef, InvalidSize, State, WindowState};
+use crate::libdecor::*;
+
+/// A `*mut libdecor_configuration` borrowed for the duration of a
+/// [`FrameHandler::configure`](super::FrameHandler::configure) callback.
+pub struct Configuration<'a> {
+    raw: NonNull<libdecor_configuration>,
+    frame: NonNull<libdecor_frame>,
+    _marker: PhantomData<&'a libdecor_configuration>,
+}
+
+impl Configuration<'_> {
+    pub(crate) unsafe fn from_raw(
+        raw: *mut libdecor_configuration,
+        frame: *mut libdecor_frame,
+    ) -> Self {
+        Self {
+            raw: unsafe { NonNull::new_unchecked(raw) },
+            frame: unsafe { NonNull::new_unchecked(frame) },
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn as_raw(&self) -> *mut libdecor_configuration {
+        self.raw.as_ptr()
+    }
+
+    /// Returns the content size suggested by the compositor, if any.
+    pub fn content_size(&self) -> Option<(u32, u32)> {
+        emit!(trace, "libdecor_configuration_get_content_size", configuration = ?self.raw, frame = ?self.frame);
+        let mut width: c_int = 0;
+        let mut height: c_int = 0;
+        let has_size = unsafe {
+            libdecor_configuration_get_content_size(
+                self.as_raw(),
+                self.frame.as_ptr(),
+                &mut width,
+                &mut height,
+            )
+        };
+        match has_size {
+            true => Some((width.try_into().ok()?, height.try_into().ok()?)),
+            false => None,
+        }
+    }
+
+    pub fn window_state(&self) -> Option<WindowState> {
+        emit!(trace, "libdecor_configuration_get_window_state", configuration = ?self.raw);
+        let mut window_state: libdecor_window_state = 0;
+        let has_window_state =
+            unsafe { libdecor_configuration_get_window_state(self.as_raw(), &mut window_state) };
+        match has_window_state {
+            true => Some(window_state.into()),
+            false => None,
+        }
+    }
+
+    /// Commits `frame` at the given content size in response to this configuration.
+    pub fn commit(&self, frame: &FrameRef, width: u32, height: u32) -> Result<(), InvalidSize> {
+        let state = State::new(width, height)?;
+        frame.commit(&state, Some(self));
+        Ok(())
+    }
+}
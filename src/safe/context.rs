@@ -0,0 +1,258 @@
+use core::{
+    cell::RefCell,
+    ffi::{CStr, c_char, c_int},
+    ptr::{self, NonNull, null_mut},
+    time::Duration,
+};
+use std::{
+    any::Any,
+    boxed::Box,
+    collections::VecDeque,
+    ffi::CString,
+    io,
+    os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd},
+    panic::{AssertUnwindSafe, catch_unwind, resume_unwind},
+    rc::Rc,
+    thread_local,
+    vec::Vec,
+};
+
+use wayland_headers::wayland_client::{wl_display, wl_surface};
+
+use super::{Error, Frame, FrameCommon, FrameData, FrameHandler, UnknownValue};
+use crate::libdecor::*;
+
+type ErrorHandler = dyn FnMut(Result<Error, UnknownValue>, &CStr);
+
+/// An owned `*mut libdecor`, unreferenced on drop.
+pub struct Context {
+    raw: NonNull<libdecor>,
+    display: *mut wl_display,
+    data: Box<ContextData>,
+}
+
+pub(crate) struct ContextData {
+    on_error: RefCell<Box<ErrorHandler>>,
+    /// Errors reported while `on_error` was running, delivered once it returns.
+    errors: RefCell<VecDeque<(Result<Error, UnknownValue>, CString)>>,
+    /// A panic caught in a callback, to be resumed once libdecor returns.
+    panic: RefCell<Option<Box<dyn Any + Send>>>,
+}
+
+thread_local! {
+    /// The data of every live context on this thread, keyed by its `*mut libdecor`, or by null
+    /// while `libdecor_new` creates it.
+    static CONTEXTS: RefCell<Vec<(*mut libdecor, *const ContextData)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+impl Context {
+    /// Calls `libdecor_new`, routing `libdecor_interface::error` to `on_error`.
+    ///
+    /// # Safety
+    ///
+    /// `display` must be a valid `wl_display` that outlives the returned context.
+    pub unsafe fn new(
+        display: *mut wl_display,
+        on_error: impl FnMut(Result<Error, UnknownValue>, &CStr) + 'static,
+    ) -> io::Result<Self> {
+        let data = Box::new(ContextData {
+            on_error: RefCell::new(Box::new(on_error)),
+            errors: RefCell::new(VecDeque::new()),
+            panic: RefCell::new(None),
+        });
+        let iface = ptr::from_ref(&LIBDECOR_INTERFACE).cast_mut();
+        emit!(trace, "libdecor_new");
+        // `libdecor_new` reports errors before returning the pointer they're keyed by.
+        data.register(null_mut());
+        let raw = unsafe { libdecor_new(display, iface) };
+        data.unregister();
+        let Some(raw) = NonNull::new(raw) else {
+            data.resume_panic();
+            return Err(io::Error::other("libdecor_new failed"));
+        };
+        data.register(raw.as_ptr());
+        let context = Self { raw, display, data };
+        context.data.resume_panic();
+        Ok(context)
+    }
+
+    pub fn as_raw(&self) -> *mut libdecor {
+        self.raw.as_ptr()
+    }
+
+    /// The `wl_display` passed to `libdecor_new`.
+    pub fn display(&self) -> *mut wl_display {
+        self.display
+    }
+
+    /// Calls `libdecor_decorate`, routing the frame's callbacks to `handler`.
+    ///
+    /// `handler` must be `'static` because a leaked [`Frame`] keeps receiving callbacks for as
+    /// long as the context is dispatched.
+    ///
+    /// # Safety
+    ///
+    /// `surface` must be a valid `wl_surface` that outlives the returned frame.
+    pub unsafe fn decorate<H: FrameHandler + 'static>(
+        &self,
+        surface: *mut wl_surface,
+        handler: H,
+    ) -> io::Result<Frame<'_, H>> {
+        let common = FrameCommon::new(&self.data, self.display, surface);
+        let data = Rc::new(FrameData::new(common, handler));
+        let iface = ptr::from_ref(FrameData::<H>::interface()).cast_mut();
+        let user_data = Rc::as_ptr(&data).cast_mut().cast();
+        emit!(trace, "libdecor_decorate", ?surface);
+        let raw = self
+            .data
+            .enter(|| unsafe { libdecor_decorate(self.as_raw(), surface, iface, user_data) });
+        match NonNull::new(raw) {
+            Some(raw) => Ok(unsafe { Frame::from_raw(raw, data) }),
+            None => Err(io::Error::other("libdecor_decorate failed")),
+        }
+    }
+
+    /// Calls `libdecor_dispatch`, waiting up to `timeout` or forever if `None`.
+    ///
+    /// Returns the number of dispatched events.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the first panic raised by a callback, once `libdecor_dispatch` returns. This
+    /// includes a frame being configured while its handler is borrowed, e.g. through
+    /// [`Frame::handler`], since the configuration could then never be committed.
+    pub fn dispatch(&self, timeout: Option<Duration>) -> io::Result<usize> {
+        let timeout = match timeout {
+            Some(timeout) => {
+                c_int::try_from(timeout.as_nanos().div_ceil(1_000_000)).unwrap_or(c_int::MAX)
+            }
+            None => -1,
+        };
+        emit!(trace, "libdecor_dispatch", timeout);
+        let result = self
+            .data
+            .enter(|| unsafe { libdecor_dispatch(self.as_raw(), timeout) });
+        match usize::try_from(result) {
+            Ok(count) => Ok(count),
+            Err(_) => Err(io::Error::last_os_error()),
+        }
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        emit!(trace, "libdecor_unref");
+        unsafe { libdecor_unref(self.as_raw()) };
+        self.data.unregister();
+        self.data.resume_panic();
+    }
+}
+
+impl AsRawFd for Context {
+    fn as_raw_fd(&self) -> RawFd {
+        emit!(trace, "libdecor_get_fd");
+        unsafe { libdecor_get_fd(self.as_raw()) }
+    }
+}
+
+impl AsFd for Context {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.as_raw_fd()) }
+    }
+}
+
+impl ContextData {
+    /// Runs `f`, a call into libdecor, then resumes any panic caught by the callbacks since the
+    /// last call.
+    pub(crate) fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        let result = f();
+        self.resume_panic();
+        result
+    }
+
+    fn resume_panic(&self) {
+        if let Some(payload) = self.panic.take() {
+            resume_unwind(payload);
+        }
+    }
+
+    /// Makes `self` the target of the `libdecor_interface` callbacks for `raw`.
+    fn register(&self, raw: *mut libdecor) {
+        CONTEXTS.with_borrow_mut(|contexts| contexts.push((raw, self)));
+    }
+
+    fn unregister(&self) {
+        CONTEXTS.with_borrow_mut(|contexts| contexts.retain(|&(_, data)| !ptr::eq(data, self)));
+    }
+
+    fn lookup(raw: *mut libdecor) -> Option<*const ContextData> {
+        CONTEXTS.with_borrow(|contexts| {
+            let find = |key| contexts.iter().rfind(|&&(raw, _)| raw == key);
+            find(raw)
+                .or_else(|| find(null_mut()))
+                .map(|&(_, data)| data)
+        })
+    }
+
+    /// Passes the queued errors to `on_error`, unless it's already running further up the stack,
+    /// in which case that call delivers them once it returns.
+    fn deliver_errors(&self) {
+        let Ok(mut on_error) = self.on_error.try_borrow_mut() else {
+            return;
+        };
+        loop {
+            let Some((kind, message)) = self.errors.borrow_mut().pop_front() else {
+                break;
+            };
+            catch_unwind_or_stash(self, || on_error(kind, &message));
+        }
+    }
+}
+
+/// Runs a callback invoked by libdecor without unwinding into C.
+///
+/// A panic is stashed in `context` and resumed once the call into libdecor that led to the
+/// callback returns, skipping the context's callbacks in between.
+pub(crate) fn catch_unwind_or_stash(context: &ContextData, f: impl FnOnce()) {
+    if context.panic.borrow().is_some() {
+        return;
+    }
+    if let Err(payload) = catch_unwind(AssertUnwindSafe(f)) {
+        *context.panic.borrow_mut() = Some(payload);
+    }
+}
+
+static LIBDECOR_INTERFACE: libdecor_interface = libdecor_interface {
+    error: Some(on_libdecor_error),
+    reserved0: None,
+    reserved1: None,
+    reserved2: None,
+    reserved3: None,
+    reserved4: None,
+    reserved5: None,
+    reserved6: None,
+    reserved7: None,
+    reserved8: None,
+    reserved9: None,
+};
+
+unsafe extern "C" fn on_libdecor_error(
+    context: *mut libdecor,
+    error: libdecor_error,
+    message: *const c_char,
+) {
+    // Errors also arrive while the application dispatches the `wl_display` itself.
+    let Some(data) = ContextData::lookup(context) else {
+        return;
+    };
+    let data = unsafe { &*data };
+    let message = match message.is_null() {
+        true => c"",
+        false => unsafe { CStr::from_ptr(message) },
+    };
+    emit!(error, "error", kind = ?Error::try_from(error), ?message);
+    let error = (Error::try_from(error), message.into());
+    data.errors.borrow_mut().push_back(error);
+    data.deliver_errors();
+}
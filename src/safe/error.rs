@@ -0,0 +1,54 @@
+use core::{error, ffi::c_int, fmt};
+
+use crate::libdecor::*;
+
+/// A typed `libdecor_error`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    CompositorIncompatible,
+    InvalidFrameConfiguration,
+}
+
+/// A raw value with no corresponding enum variant, e.g. from a newer version of libdecor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownValue(pub c_int);
+
+impl TryFrom<libdecor_error> for Error {
+    type Error = UnknownValue;
+
+    fn try_from(error: libdecor_error) -> Result<Self, UnknownValue> {
+        match error {
+            LIBDECOR_ERROR_COMPOSITOR_INCOMPATIBLE => Ok(Self::CompositorIncompatible),
+            LIBDECOR_ERROR_INVALID_FRAME_CONFIGURATION => Ok(Self::InvalidFrameConfiguration),
+            _ => Err(UnknownValue(error)),
+        }
+    }
+}
+
+impl From<Error> for libdecor_error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::CompositorIncompatible => LIBDECOR_ERROR_COMPOSITOR_INCOMPATIBLE,
+            Error::InvalidFrameConfiguration => LIBDECOR_ERROR_INVALID_FRAME_CONFIGURATION,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::CompositorIncompatible => "compositor is incompatible with libdecor",
+            Self::InvalidFrameConfiguration => "invalid frame configuration",
+        })
+    }
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for UnknownValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown value {}", self.0)
+    }
+}
+
+impl error::Error for UnknownValue {}
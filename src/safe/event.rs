@@ -0,0 +1,221 @@
+use core::{cell::RefCell, ffi::CStr, task::Waker, time::Duration};
+use std::{collections::VecDeque, ffi::CString, io, rc::Rc};
+
+use wayland_headers::wayland_client::wl_display;
+
+use super::{
+    Configuration, Context, Error, FrameHandler, FrameId, FrameRef, InvalidSize, UnknownValue,
+    WindowState,
+};
+
+/// A [`FrameHandler`] callback recorded by a [`QueuedFrameHandler`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FrameEvent {
+    /// The frame was configured and has already been committed in response, at the size in
+    /// `committed`.
+    ///
+    /// If that size was invalid, `committed` holds the error and the configuration was left
+    /// unacknowledged, so the frame won't be mapped or resized until a later configure succeeds.
+    Configure {
+        size: Option<(u32, u32)>,
+        state: Option<WindowState>,
+        committed: Result<(u32, u32), InvalidSize>,
+    },
+    Close,
+    Commit,
+    DismissPopup {
+        seat: CString,
+    },
+}
+
+/// A `libdecor_interface` callback recorded by an [`EventQueue`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContextEvent {
+    Error {
+        kind: Result<Error, UnknownValue>,
+        message: CString,
+    },
+}
+
+/// An event returned by [`EventQueue::poll_events`] and [`EventQueue::wait_events`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Context(ContextEvent),
+    Frame(FrameId, FrameEvent),
+}
+
+/// A [`Context`] whose callbacks are queued as [`Event`]s for a main loop to poll, instead of
+/// being handled from within `libdecor_dispatch`.
+///
+/// Frames decorated with handlers from [`EventQueue::queue`] push their events onto the same
+/// queue.
+pub struct EventQueue {
+    context: Context,
+    queue: FrameEventQueue,
+    context_events: Rc<RefCell<VecDeque<ContextEvent>>>,
+}
+
+/// The events drained from an [`EventQueue`], context events first.
+pub struct Events<'a> {
+    queue: &'a EventQueue,
+}
+
+/// A shared queue of [`FrameEvent`]s filled by [`QueuedFrameHandler`]s, each tagged with the
+/// frame it came from.
+#[derive(Clone, Default)]
+pub struct FrameEventQueue {
+    inner: Rc<RefCell<QueueInner>>,
+}
+
+#[derive(Default)]
+struct QueueInner {
+    events: VecDeque<(FrameId, FrameEvent)>,
+    waker: Option<Waker>,
+}
+
+/// A [`FrameHandler`] that pushes every callback onto a [`FrameEventQueue`].
+///
+/// Configurations are committed immediately, at the suggested content size if there is one or at
+/// the last committed size otherwise.
+pub struct QueuedFrameHandler {
+    queue: FrameEventQueue,
+    size: (u32, u32),
+}
+
+impl FrameEventQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a handler for a new frame that starts out `width` by `height`.
+    ///
+    /// Commits fail while the size is zero or above `c_int::MAX`, which
+    /// [`FrameEvent::Configure`] reports.
+    pub fn handler(&self, width: u32, height: u32) -> QueuedFrameHandler {
+        QueuedFrameHandler {
+            queue: self.clone(),
+            size: (width, height),
+        }
+    }
+
+    pub fn pop(&self) -> Option<(FrameId, FrameEvent)> {
+        self.inner.borrow_mut().events.pop_front()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().events.is_empty()
+    }
+
+    fn push(&self, frame: &FrameRef, event: FrameEvent) {
+        let mut inner = self.inner.borrow_mut();
+        inner.events.push_back((frame.id(), event));
+        if let Some(waker) = inner.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Wakes `waker` the next time an event is pushed.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn register_waker(&self, waker: &Waker) {
+        self.inner.borrow_mut().waker = Some(waker.clone());
+    }
+}
+
+impl FrameHandler for QueuedFrameHandler {
+    fn configure(&mut self, frame: &FrameRef, configuration: Configuration<'_>) {
+        let size = configuration.content_size();
+        let state = configuration.window_state();
+        if let Some(size) = size {
+            self.size = size;
+        }
+        let (width, height) = self.size;
+        let committed = configuration
+            .commit(frame, width, height)
+            .map(|()| (width, height));
+        let event = FrameEvent::Configure {
+            size,
+            state,
+            committed,
+        };
+        self.queue.push(frame, event);
+    }
+
+    fn close(&mut self, frame: &FrameRef) {
+        self.queue.push(frame, FrameEvent::Close);
+    }
+
+    fn commit(&mut self, frame: &FrameRef) {
+        self.queue.push(frame, FrameEvent::Commit);
+    }
+
+    fn dismiss_popup(&mut self, frame: &FrameRef, seat_name: &CStr) {
+        let seat = seat_name.into();
+        self.queue.push(frame, FrameEvent::DismissPopup { seat });
+    }
+}
+
+impl EventQueue {
+    /// Calls `libdecor_new`, queuing `libdecor_interface::error` as [`ContextEvent::Error`].
+    ///
+    /// # Safety
+    ///
+    /// `display` must be a valid `wl_display` that outlives the returned queue.
+    pub unsafe fn new(display: *mut wl_display) -> io::Result<Self> {
+        let context_events = Rc::new(RefCell::new(VecDeque::new()));
+        let on_error = {
+            let context_events = context_events.clone();
+            move |kind, message: &CStr| {
+                let message = message.into();
+                let event = ContextEvent::Error { kind, message };
+                context_events.borrow_mut().push_back(event);
+            }
+        };
+        Ok(Self {
+            context: unsafe { Context::new(display, on_error) }?,
+            queue: FrameEventQueue::new(),
+            context_events,
+        })
+    }
+
+    /// Returns the context, which frames borrow.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns the queue whose handlers deliver their events through this queue.
+    pub fn queue(&self) -> FrameEventQueue {
+        self.queue.clone()
+    }
+
+    /// Dispatches without waiting and drains the queued events.
+    pub fn poll_events(&self) -> io::Result<Events<'_>> {
+        self.context.dispatch(Some(Duration::ZERO))?;
+        Ok(Events { queue: self })
+    }
+
+    /// Dispatches, waiting up to `timeout` or forever if `None` unless events are already queued,
+    /// and drains the queued events.
+    pub fn wait_events(&self, timeout: Option<Duration>) -> io::Result<Events<'_>> {
+        let timeout = match self.context_events.borrow().is_empty() && self.queue.is_empty() {
+            true => timeout,
+            false => Some(Duration::ZERO),
+        };
+        self.context.dispatch(timeout)?;
+        Ok(Events { queue: self })
+    }
+}
+
+impl Iterator for Events<'_> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        let context_event = self.queue.context_events.borrow_mut().pop_front();
+        match context_event {
+            Some(event) => Some(Event::Context(event)),
+            None => {
+                let (frame_id, event) = self.queue.queue.pop()?;
+                Some(Event::Frame(frame_id, event))
+            }
+        }
+    }
+}
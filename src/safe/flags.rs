@@ -0,0 +1,141 @@
+use core::{
+    fmt,
+    ops::{BitAnd, BitOr, BitOrAssign},
+};
+
+use crate::libdecor::*;
+
+macro_rules! flags {
+    (
+        $(#[$attr:meta])*
+        pub struct $Flags:ident: $raw:ty {
+            $($(#[$flag_attr:meta])* const $FLAG:ident = $value:expr;)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $Flags($raw);
+
+        impl $Flags {
+            $($(#[$flag_attr])* pub const $FLAG: Self = Self($value);)*
+
+            const NAMED: &[(&str, Self)] = &[$($(#[$flag_attr])* (stringify!($FLAG), Self::$FLAG)),*];
+
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            pub const fn bits(self) -> $raw {
+                self.0
+            }
+
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            pub fn insert(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+
+            pub fn remove(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+
+            /// Iterates over each set flag, yielding any unnamed bits as a single final value.
+            pub fn iter(self) -> impl Iterator<Item = Self> {
+                let unnamed = Self::NAMED
+                    .iter()
+                    .fold(self.0, |bits, &(_, flag)| bits & !flag.0);
+                Self::NAMED
+                    .iter()
+                    .map(|&(_, flag)| flag)
+                    .filter(move |&flag| self.contains(flag))
+                    .chain((unnamed != 0).then_some(Self(unnamed)))
+            }
+        }
+
+        impl From<$raw> for $Flags {
+            fn from(bits: $raw) -> Self {
+                Self(bits)
+            }
+        }
+
+        impl From<$Flags> for $raw {
+            fn from(flags: $Flags) -> Self {
+                flags.0
+            }
+        }
+
+        impl BitOr for $Flags {
+            type Output = Self;
+
+            fn bitor(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+        }
+
+        impl BitOrAssign for $Flags {
+            fn bitor_assign(&mut self, other: Self) {
+                self.insert(other);
+            }
+        }
+
+        impl BitAnd for $Flags {
+            type Output = Self;
+
+            fn bitand(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+        }
+
+        impl fmt::Debug for $Flags {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}(", stringify!($Flags))?;
+                if self.is_empty() {
+                    write!(f, "{:#x}", self.0)?;
+                }
+                for (index, flag) in self.iter().enumerate() {
+                    if index != 0 {
+                        f.write_str(" | ")?;
+                    }
+                    match Self::NAMED.iter().find(|&&(_, named)| named == flag) {
+                        Some((name, _)) => f.write_str(name)?,
+                        None => write!(f, "{:#x}", flag.0)?,
+                    }
+                }
+                f.write_str(")")
+            }
+        }
+    };
+}
+
+flags! {
+    /// A typed `libdecor_capabilities`.
+    pub struct Capabilities: libdecor_capabilities {
+        const MOVE = LIBDECOR_ACTION_MOVE;
+        const RESIZE = LIBDECOR_ACTION_RESIZE;
+        const MINIMIZE = LIBDECOR_ACTION_MINIMIZE;
+        const FULLSCREEN = LIBDECOR_ACTION_FULLSCREEN;
+        const CLOSE = LIBDECOR_ACTION_CLOSE;
+    }
+}
+
+flags! {
+    /// A typed `libdecor_window_state`.
+    pub struct WindowState: libdecor_window_state {
+        const ACTIVE = LIBDECOR_WINDOW_STATE_ACTIVE;
+        const MAXIMIZED = LIBDECOR_WINDOW_STATE_MAXIMIZED;
+        const FULLSCREEN = LIBDECOR_WINDOW_STATE_FULLSCREEN;
+        const TILED_LEFT = LIBDECOR_WINDOW_STATE_TILED_LEFT;
+        const TILED_RIGHT = LIBDECOR_WINDOW_STATE_TILED_RIGHT;
+        const TILED_TOP = LIBDECOR_WINDOW_STATE_TILED_TOP;
+        const TILED_BOTTOM = LIBDECOR_WINDOW_STATE_TILED_BOTTOM;
+        #[cfg(feature = "libdecor-0-2-0")]
+        const SUSPENDED = LIBDECOR_WINDOW_STATE_SUSPENDED;
+    }
+}
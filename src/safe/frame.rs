@@ -0,0 +1,445 @@
+use core::{
+    cell::{Cell, Ref, RefMut},
+    ffi::{CStr, c_int},
+    marker::PhantomData,
+    ops::Deref,
+    ptr::{NonNull, null_mut},
+};
+use std::rc::Rc;
+
+use wayland_headers::{wayland_client::*, xdg_shell_client_protocol::*};
+
+use super::{
+    Capabilities, Configuration, Context, ContextData, FrameData, FrameHandler, ResizeEdge, State,
+};
+use crate::libdecor::*;
+
+/// A borrowed `*mut libdecor_frame`.
+pub struct FrameRef {
+    raw: NonNull<libdecor_frame>,
+    common: NonNull<FrameCommon>,
+}
+
+/// The state shared by every [`FrameRef`] to a frame decorated by [`Context::decorate`].
+pub(crate) struct FrameCommon {
+    context: NonNull<ContextData>,
+    display: *mut wl_display,
+    surface: *mut wl_surface,
+    min_content_size: Cell<(i32, i32)>,
+    max_content_size: Cell<(i32, i32)>,
+    pending_maximized: Cell<bool>,
+    pending_fullscreen: Cell<Option<*mut wl_output>>,
+}
+
+/// Identifies a frame for as long as any reference to it remains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FrameId(NonNull<libdecor_frame>);
+
+/// An owned reference to a `*mut libdecor_frame` along with the handler passed to
+/// `libdecor_decorate` as its user data.
+///
+/// Cloning calls `libdecor_frame_ref` and dropping calls `libdecor_frame_unref`. The handler is
+/// dropped along with the last clone, after the frame has been unreferenced for the last time.
+pub struct Frame<'ctx, H> {
+    frame: FrameRef,
+    data: Rc<FrameData<H>>,
+    _context: PhantomData<&'ctx Context>,
+}
+
+impl FrameCommon {
+    pub(crate) fn new(
+        context: &ContextData,
+        display: *mut wl_display,
+        surface: *mut wl_surface,
+    ) -> Self {
+        Self {
+            context: NonNull::from(context),
+            display,
+            surface,
+            min_content_size: Cell::default(),
+            max_content_size: Cell::default(),
+            pending_maximized: Cell::new(false),
+            pending_fullscreen: Cell::new(None),
+        }
+    }
+
+    /// The data of the context that decorated the frame, which outlives it.
+    pub(crate) fn context(&self) -> &ContextData {
+        unsafe { self.context.as_ref() }
+    }
+}
+
+impl FrameRef {
+    pub(crate) unsafe fn from_raw(raw: *mut libdecor_frame, common: &FrameCommon) -> Self {
+        Self {
+            raw: unsafe { NonNull::new_unchecked(raw) },
+            common: NonNull::from(common),
+        }
+    }
+
+    fn common(&self) -> &FrameCommon {
+        unsafe { self.common.as_ref() }
+    }
+
+    /// Calls into libdecor through the frame's context, resuming any panic raised by the callbacks
+    /// libdecor makes in the meantime.
+    fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.common().context().enter(f)
+    }
+
+    pub fn as_raw(&self) -> *mut libdecor_frame {
+        self.raw.as_ptr()
+    }
+
+    pub fn id(&self) -> FrameId {
+        FrameId(self.raw)
+    }
+
+    /// The `wl_display` of the context that decorated the frame.
+    pub fn display(&self) -> *mut wl_display {
+        self.common().display
+    }
+
+    /// The `wl_surface` passed to `libdecor_decorate`.
+    pub fn surface(&self) -> *mut wl_surface {
+        self.common().surface
+    }
+
+    pub fn set_visibility(&self, visible: bool) {
+        emit!(trace, "libdecor_frame_set_visibility", frame = ?self.id(), visible);
+        self.enter(|| unsafe { libdecor_frame_set_visibility(self.as_raw(), visible) });
+    }
+
+    pub fn is_visible(&self) -> bool {
+        emit!(trace, "libdecor_frame_is_visible", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_is_visible(self.as_raw()) })
+    }
+
+    pub fn set_parent(&self, parent: Option<&FrameRef>) {
+        emit!(trace, "libdecor_frame_set_parent", frame = ?self.id(), parent = ?parent.map(FrameRef::id));
+        let parent = parent.map_or(null_mut(), FrameRef::as_raw);
+        self.enter(|| unsafe { libdecor_frame_set_parent(self.as_raw(), parent) });
+    }
+
+    pub fn set_title(&self, title: &CStr) {
+        emit!(trace, "libdecor_frame_set_title", frame = ?self.id(), ?title);
+        self.enter(|| unsafe { libdecor_frame_set_title(self.as_raw(), title.as_ptr()) });
+    }
+
+    pub fn title(&self) -> Option<&CStr> {
+        emit!(trace, "libdecor_frame_get_title", frame = ?self.id());
+        let title = self.enter(|| unsafe { libdecor_frame_get_title(self.as_raw()) });
+        match title.is_null() {
+            true => None,
+            false => Some(unsafe { CStr::from_ptr(title) }),
+        }
+    }
+
+    pub fn set_app_id(&self, app_id: &CStr) {
+        emit!(trace, "libdecor_frame_set_app_id", frame = ?self.id(), ?app_id);
+        self.enter(|| unsafe { libdecor_frame_set_app_id(self.as_raw(), app_id.as_ptr()) });
+    }
+
+    pub fn set_capabilities(&self, capabilities: Capabilities) {
+        emit!(trace, "libdecor_frame_set_capabilities", frame = ?self.id(), ?capabilities);
+        self.enter(|| unsafe {
+            libdecor_frame_set_capabilities(self.as_raw(), capabilities.into())
+        });
+    }
+
+    pub fn unset_capabilities(&self, capabilities: Capabilities) {
+        emit!(trace, "libdecor_frame_unset_capabilities", frame = ?self.id(), ?capabilities);
+        self.enter(|| unsafe {
+            libdecor_frame_unset_capabilities(self.as_raw(), capabilities.into())
+        });
+    }
+
+    pub fn has_capability(&self, capability: Capabilities) -> bool {
+        emit!(trace, "libdecor_frame_has_capability", frame = ?self.id(), ?capability);
+        self.enter(|| unsafe { libdecor_frame_has_capability(self.as_raw(), capability.into()) })
+    }
+
+    /// # Safety
+    ///
+    /// `wl_seat` must be a valid `wl_seat`.
+    pub unsafe fn show_window_menu(&self, wl_seat: *mut wl_seat, serial: u32, x: i32, y: i32) {
+        emit!(trace, "libdecor_frame_show_window_menu", frame = ?self.id(), serial, x, y);
+        self.enter(|| unsafe {
+            libdecor_frame_show_window_menu(self.as_raw(), wl_seat, serial, x, y)
+        });
+    }
+
+    pub fn popup_grab(&self, seat_name: &CStr) {
+        emit!(trace, "libdecor_frame_popup_grab", frame = ?self.id(), ?seat_name);
+        self.enter(|| unsafe { libdecor_frame_popup_grab(self.as_raw(), seat_name.as_ptr()) });
+    }
+
+    pub fn popup_ungrab(&self, seat_name: &CStr) {
+        emit!(trace, "libdecor_frame_popup_ungrab", frame = ?self.id(), ?seat_name);
+        self.enter(|| unsafe { libdecor_frame_popup_ungrab(self.as_raw(), seat_name.as_ptr()) });
+    }
+
+    /// Translates surface coordinates to frame coordinates.
+    pub fn translate_coordinate(&self, surface_x: i32, surface_y: i32) -> (i32, i32) {
+        emit!(trace, "libdecor_frame_translate_coordinate", frame = ?self.id(), surface_x, surface_y);
+        let mut frame_x: c_int = 0;
+        let mut frame_y: c_int = 0;
+        self.enter(|| unsafe {
+            libdecor_frame_translate_coordinate(
+                self.as_raw(),
+                surface_x,
+                surface_y,
+                &mut frame_x,
+                &mut frame_y,
+            )
+        });
+        (frame_x, frame_y)
+    }
+
+    pub fn set_min_content_size(&self, content_width: i32, content_height: i32) {
+        emit!(trace, "libdecor_frame_set_min_content_size", frame = ?self.id(), content_width, content_height);
+        let min_content_size = (content_width, content_height);
+        self.common().min_content_size.set(min_content_size);
+        self.enter(|| unsafe {
+            libdecor_frame_set_min_content_size(self.as_raw(), content_width, content_height)
+        });
+    }
+
+    pub fn set_max_content_size(&self, content_width: i32, content_height: i32) {
+        emit!(trace, "libdecor_frame_set_max_content_size", frame = ?self.id(), content_width, content_height);
+        let max_content_size = (content_width, content_height);
+        self.common().max_content_size.set(max_content_size);
+        self.enter(|| unsafe {
+            libdecor_frame_set_max_content_size(self.as_raw(), content_width, content_height)
+        });
+    }
+
+    /// Returns the minimum content size.
+    ///
+    /// Where `libdecor_frame_get_min_content_size` isn't available, this is the last size passed
+    /// to [`FrameRef::set_min_content_size`].
+    pub fn min_content_size(&self) -> (i32, i32) {
+        #[cfg(feature = "weak")]
+        let get = crate::weak::libdecor_frame_get_min_content_size();
+        #[cfg(all(not(feature = "weak"), feature = "libdecor-0-1-1"))]
+        let get = Some(libdecor_frame_get_min_content_size);
+        #[cfg(not(any(feature = "weak", feature = "libdecor-0-1-1")))]
+        let get: Option<GetContentSize> = None;
+
+        match get {
+            Some(get) => {
+                emit!(trace, "libdecor_frame_get_min_content_size", frame = ?self.id());
+                self.get_content_size(get)
+            }
+            None => self.common().min_content_size.get(),
+        }
+    }
+
+    /// Returns the maximum content size.
+    ///
+    /// Where `libdecor_frame_get_max_content_size` isn't available, this is the last size passed
+    /// to [`FrameRef::set_max_content_size`].
+    pub fn max_content_size(&self) -> (i32, i32) {
+        #[cfg(feature = "weak")]
+        let get = crate::weak::libdecor_frame_get_max_content_size();
+        #[cfg(all(not(feature = "weak"), feature = "libdecor-0-1-1"))]
+        let get = Some(libdecor_frame_get_max_content_size);
+        #[cfg(not(any(feature = "weak", feature = "libdecor-0-1-1")))]
+        let get: Option<GetContentSize> = None;
+
+        match get {
+            Some(get) => {
+                emit!(trace, "libdecor_frame_get_max_content_size", frame = ?self.id());
+                self.get_content_size(get)
+            }
+            None => self.common().max_content_size.get(),
+        }
+    }
+
+    fn get_content_size(&self, get: GetContentSize) -> (i32, i32) {
+        let mut content_width: c_int = 0;
+        let mut content_height: c_int = 0;
+        self.enter(|| unsafe { get(self.as_raw(), &mut content_width, &mut content_height) });
+        (content_width, content_height)
+    }
+
+    /// # Safety
+    ///
+    /// `wl_seat` must be a valid `wl_seat`.
+    pub unsafe fn resize(&self, wl_seat: *mut wl_seat, serial: u32, edge: ResizeEdge) {
+        emit!(trace, "libdecor_frame_resize", frame = ?self.id(), serial, ?edge);
+        self.enter(|| unsafe {
+            libdecor_frame_resize(self.as_raw(), wl_seat, serial, edge.into())
+        });
+    }
+
+    /// # Safety
+    ///
+    /// `wl_seat` must be a valid `wl_seat`.
+    pub unsafe fn r#move(&self, wl_seat: *mut wl_seat, serial: u32) {
+        emit!(trace, "libdecor_frame_move", frame = ?self.id(), serial);
+        self.enter(|| unsafe { libdecor_frame_move(self.as_raw(), wl_seat, serial) });
+    }
+
+    pub fn commit(&self, state: &State, configuration: Option<&Configuration<'_>>) {
+        emit!(trace, "libdecor_frame_commit", frame = ?self.id(), configured = configuration.is_some());
+        let configuration = configuration.map_or(null_mut(), Configuration::as_raw);
+        self.enter(|| unsafe {
+            libdecor_frame_commit(self.as_raw(), state.as_raw(), configuration)
+        });
+    }
+
+    pub fn set_minimized(&self) {
+        emit!(trace, "libdecor_frame_set_minimized", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_set_minimized(self.as_raw()) });
+    }
+
+    pub fn set_maximized(&self) {
+        emit!(trace, "libdecor_frame_set_maximized", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_set_maximized(self.as_raw()) });
+    }
+
+    pub fn unset_maximized(&self) {
+        emit!(trace, "libdecor_frame_unset_maximized", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_unset_maximized(self.as_raw()) });
+    }
+
+    /// # Safety
+    ///
+    /// `output` must be null or a valid `wl_output`.
+    pub unsafe fn set_fullscreen(&self, output: *mut wl_output) {
+        emit!(trace, "libdecor_frame_set_fullscreen", frame = ?self.id(), ?output);
+        self.enter(|| unsafe { libdecor_frame_set_fullscreen(self.as_raw(), output) });
+    }
+
+    pub fn unset_fullscreen(&self) {
+        emit!(trace, "libdecor_frame_unset_fullscreen", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_unset_fullscreen(self.as_raw()) });
+    }
+
+    pub fn is_floating(&self) -> bool {
+        emit!(trace, "libdecor_frame_is_floating", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_is_floating(self.as_raw()) })
+    }
+
+    pub fn close(&self) {
+        emit!(trace, "libdecor_frame_close", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_close(self.as_raw()) });
+    }
+
+    pub fn map(&self) {
+        emit!(trace, "libdecor_frame_map", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_map(self.as_raw()) });
+    }
+
+    pub fn xdg_surface(&self) -> *mut xdg_surface {
+        emit!(trace, "libdecor_frame_get_xdg_surface", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_get_xdg_surface(self.as_raw()) })
+    }
+
+    pub fn xdg_toplevel(&self) -> *mut xdg_toplevel {
+        emit!(trace, "libdecor_frame_get_xdg_toplevel", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_get_xdg_toplevel(self.as_raw()) })
+    }
+
+    /// Requests the maximized and fullscreen states as soon as the frame has an `xdg_toplevel`,
+    /// which libdecor only creates once the context has finished initializing, and which its
+    /// `libdecor_frame_set_maximized` and `libdecor_frame_set_fullscreen` use unchecked.
+    ///
+    /// # Safety
+    ///
+    /// `fullscreen` must be null or a valid `wl_output` until it's requested.
+    pub(crate) unsafe fn request_window_state(
+        &self,
+        maximized: bool,
+        fullscreen: Option<*mut wl_output>,
+    ) {
+        self.common().pending_maximized.set(maximized);
+        self.common().pending_fullscreen.set(fullscreen);
+        self.flush_window_state();
+    }
+
+    /// Sends the states left pending by [`FrameRef::request_window_state`] if the frame now has
+    /// an `xdg_toplevel`.
+    pub(crate) fn flush_window_state(&self) {
+        let common = self.common();
+        if !common.pending_maximized.get() && common.pending_fullscreen.get().is_none() {
+            return;
+        }
+        if self.xdg_toplevel().is_null() {
+            return;
+        }
+        if common.pending_maximized.replace(false) {
+            self.set_maximized();
+        }
+        if let Some(output) = common.pending_fullscreen.take() {
+            unsafe { self.set_fullscreen(output) };
+        }
+    }
+}
+
+type GetContentSize = unsafe extern "C" fn(*const libdecor_frame, *mut c_int, *mut c_int);
+
+impl<H: FrameHandler> Frame<'_, H> {
+    /// Takes ownership of a reference to `raw`, which must have been returned by
+    /// `libdecor_decorate` with a pointer to `data` as its user data.
+    pub(crate) unsafe fn from_raw(raw: NonNull<libdecor_frame>, data: Rc<FrameData<H>>) -> Self {
+        Self {
+            frame: FrameRef {
+                raw,
+                common: NonNull::from(&data.common),
+            },
+            data,
+            _context: PhantomData,
+        }
+    }
+
+    /// Borrows the handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handler is currently borrowed mutably, e.g. from within one of its own
+    /// callbacks.
+    pub fn handler(&self) -> Ref<'_, H> {
+        self.data.handler.borrow()
+    }
+
+    /// Mutably borrows the handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handler is currently borrowed, e.g. from within one of its own callbacks.
+    pub fn handler_mut(&self) -> RefMut<'_, H> {
+        self.data.handler.borrow_mut()
+    }
+}
+
+impl<H> Clone for Frame<'_, H> {
+    fn clone(&self) -> Self {
+        emit!(trace, "libdecor_frame_ref", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_ref(self.as_raw()) });
+        Self {
+            frame: FrameRef {
+                raw: self.raw,
+                common: self.common,
+            },
+            data: self.data.clone(),
+            _context: PhantomData,
+        }
+    }
+}
+
+impl<H> Drop for Frame<'_, H> {
+    fn drop(&mut self) {
+        emit!(trace, "libdecor_frame_unref", frame = ?self.id());
+        self.enter(|| unsafe { libdecor_frame_unref(self.as_raw()) });
+    }
+}
+
+impl<H> Deref for Frame<'_, H> {
+    type Target = FrameRef;
+
+    fn deref(&self) -> &FrameRef {
+        &self.frame
+    }
+}
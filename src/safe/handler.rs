@@ -0,0 +1,181 @@
+use core::{
+    cell::RefCell,
+    ffi::{CStr, c_char, c_void},
+};
+use std::{collections::VecDeque, ffi::CString};
+
+use super::{Configuration, FrameCommon, FrameRef, catch_unwind_or_stash};
+use crate::libdecor::*;
+
+/// Receives the callbacks of a `libdecor_frame_interface`.
+///
+/// Callbacks that arrive while the handler is already running, like the `commit` requested by
+/// calling [`FrameRef::set_title`] from within `configure`, are delivered once the running
+/// callback returns. A `configure` can't wait, so it panics instead if the handler is borrowed.
+pub trait FrameHandler {
+    fn configure(&mut self, frame: &FrameRef, configuration: Configuration<'_>);
+
+    fn close(&mut self, frame: &FrameRef);
+
+    fn commit(&mut self, frame: &FrameRef);
+
+    fn dismiss_popup(&mut self, frame: &FrameRef, seat_name: &CStr) {
+        let _ = (frame, seat_name);
+    }
+}
+
+/// The user data passed to `libdecor_decorate`.
+pub(crate) struct FrameData<H> {
+    pub(crate) common: FrameCommon,
+    pub(crate) handler: RefCell<H>,
+    deferred: RefCell<VecDeque<Deferred>>,
+}
+
+enum Callback<'a> {
+    Configure(Configuration<'a>),
+    Close,
+    Commit,
+    DismissPopup(&'a CStr),
+}
+
+enum Deferred {
+    Close,
+    Commit,
+    DismissPopup(CString),
+}
+
+impl<H: FrameHandler> FrameData<H> {
+    pub(crate) fn new(common: FrameCommon, handler: H) -> Self {
+        Self {
+            common,
+            handler: RefCell::new(handler),
+            deferred: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn interface() -> &'static libdecor_frame_interface {
+        &const {
+            libdecor_frame_interface {
+                configure: Some(on_libdecor_frame_configure::<H>),
+                close: Some(on_libdecor_frame_close::<H>),
+                commit: Some(on_libdecor_frame_commit::<H>),
+                dismiss_popup: Some(on_libdecor_frame_dismiss_popup::<H>),
+                reserved0: None,
+                reserved1: None,
+                reserved2: None,
+                reserved3: None,
+                reserved4: None,
+                reserved5: None,
+                reserved6: None,
+                reserved7: None,
+                reserved8: None,
+                reserved9: None,
+            }
+        }
+    }
+
+    fn handle(&self, frame: &FrameRef, callback: Callback<'_>) {
+        let Ok(mut handler) = self.handler.try_borrow_mut() else {
+            // A configuration can't outlive its callback, so it must be committed now or never.
+            let deferred = match callback {
+                Callback::Configure(_) => {
+                    panic!("frame configured while its handler was borrowed")
+                }
+                Callback::Close => Deferred::Close,
+                Callback::Commit => Deferred::Commit,
+                Callback::DismissPopup(seat_name) => Deferred::DismissPopup(seat_name.into()),
+            };
+            self.deferred.borrow_mut().push_back(deferred);
+            return;
+        };
+
+        callback.deliver(&mut *handler, frame);
+        loop {
+            let deferred = self.deferred.borrow_mut().pop_front();
+            let Some(deferred) = deferred else {
+                break;
+            };
+            let callback = match &deferred {
+                Deferred::Close => Callback::Close,
+                Deferred::Commit => Callback::Commit,
+                Deferred::DismissPopup(seat_name) => Callback::DismissPopup(seat_name),
+            };
+            callback.deliver(&mut *handler, frame);
+        }
+    }
+
+    unsafe fn from_user_data<'a>(user_data: *mut c_void) -> &'a Self {
+        let data = user_data.cast::<Self>();
+        unsafe { &*data }
+    }
+}
+
+impl Callback<'_> {
+    fn deliver(self, handler: &mut impl FrameHandler, frame: &FrameRef) {
+        match self {
+            Self::Configure(configuration) => handler.configure(frame, configuration),
+            Self::Close => handler.close(frame),
+            Self::Commit => handler.commit(frame),
+            Self::DismissPopup(seat_name) => handler.dismiss_popup(frame, seat_name),
+        }
+    }
+}
+
+unsafe extern "C" fn on_libdecor_frame_configure<H: FrameHandler>(
+    frame: *mut libdecor_frame,
+    configuration: *mut libdecor_configuration,
+    user_data: *mut c_void,
+) {
+    let data = unsafe { FrameData::<H>::from_user_data(user_data) };
+    catch_unwind_or_stash(data.common.context(), || {
+        let frame = unsafe { FrameRef::from_raw(frame, &data.common) };
+        frame.flush_window_state();
+        let configuration = unsafe { Configuration::from_raw(configuration, frame.as_raw()) };
+        emit!(
+            debug,
+            "configure",
+            frame = ?frame.id(),
+            content_size = ?configuration.content_size(),
+            window_state = ?configuration.window_state()
+        );
+        data.handle(&frame, Callback::Configure(configuration));
+    });
+}
+
+unsafe extern "C" fn on_libdecor_frame_close<H: FrameHandler>(
+    frame: *mut libdecor_frame,
+    user_data: *mut c_void,
+) {
+    let data = unsafe { FrameData::<H>::from_user_data(user_data) };
+    catch_unwind_or_stash(data.common.context(), || {
+        let frame = unsafe { FrameRef::from_raw(frame, &data.common) };
+        emit!(debug, "close", frame = ?frame.id());
+        data.handle(&frame, Callback::Close);
+    });
+}
+
+unsafe extern "C" fn on_libdecor_frame_commit<H: FrameHandler>(
+    frame: *mut libdecor_frame,
+    user_data: *mut c_void,
+) {
+    let data = unsafe { FrameData::<H>::from_user_data(user_data) };
+    catch_unwind_or_stash(data.common.context(), || {
+        let frame = unsafe { FrameRef::from_raw(frame, &data.common) };
+        emit!(debug, "commit", frame = ?frame.id());
+        data.handle(&frame, Callback::Commit);
+    });
+}
+
+unsafe extern "C" fn on_libdecor_frame_dismiss_popup<H: FrameHandler>(
+    frame: *mut libdecor_frame,
+    seat_name: *const c_char,
+    user_data: *mut c_void,
+) {
+    let data = unsafe { FrameData::<H>::from_user_data(user_data) };
+    catch_unwind_or_stash(data.common.context(), || {
+        let frame = unsafe { FrameRef::from_raw(frame, &data.common) };
+        let seat_name = unsafe { CStr::from_ptr(seat_name) };
+        emit!(debug, "dismiss_popup", frame = ?frame.id(), ?seat_name);
+        data.handle(&frame, Callback::DismissPopup(seat_name));
+    });
+}
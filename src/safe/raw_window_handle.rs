@@ -0,0 +1,56 @@
+use core::ptr::NonNull;
+
+use ::raw_window_handle::{
+    DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, WaylandDisplayHandle,
+    WaylandWindowHandle, WindowHandle,
+};
+use wayland_headers::wayland_client::{wl_display, wl_surface};
+
+use super::{Context, Frame, FrameRef};
+
+#[cfg(all(test, feature = "mock"))]
+mod test;
+
+impl HasDisplayHandle for Context {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        display_handle(self.display())
+    }
+}
+
+impl HasDisplayHandle for FrameRef {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        display_handle(self.display())
+    }
+}
+
+impl HasWindowHandle for FrameRef {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        window_handle(self.surface())
+    }
+}
+
+impl<H> HasDisplayHandle for Frame<'_, H> {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        display_handle(self.display())
+    }
+}
+
+impl<H> HasWindowHandle for Frame<'_, H> {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        window_handle(self.surface())
+    }
+}
+
+fn display_handle<'a>(display: *mut wl_display) -> Result<DisplayHandle<'a>, HandleError> {
+    let display = NonNull::new(display.cast()).ok_or(HandleError::Unavailable)?;
+    let raw = WaylandDisplayHandle::new(display).into();
+    // `Context::new` requires the display to outlive the context and every frame it decorates.
+    Ok(unsafe { DisplayHandle::borrow_raw(raw) })
+}
+
+fn window_handle<'a>(surface: *mut wl_surface) -> Result<WindowHandle<'a>, HandleError> {
+    let surface = NonNull::new(surface.cast()).ok_or(HandleError::Unavailable)?;
+    let raw = WaylandWindowHandle::new(surface).into();
+    // `Context::decorate` requires the surface to outlive the frame.
+    Ok(unsafe { WindowHandle::borrow_raw(raw) })
+}
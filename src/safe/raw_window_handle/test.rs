@@ -0,0 +1,57 @@
+use core::ptr::{null_mut, without_provenance_mut};
+
+use ::raw_window_handle::{RawDisplayHandle, RawWindowHandle};
+
+use super::*;
+use crate::{
+    mock::{contexts, frames},
+    safe::FrameEventQueue,
+};
+
+fn wayland_display(handle: &impl HasDisplayHandle) -> *mut wl_display {
+    match handle.display_handle().unwrap().as_raw() {
+        RawDisplayHandle::Wayland(handle) => handle.display.as_ptr().cast(),
+        raw => panic!("unexpected display handle {raw:?}"),
+    }
+}
+
+fn wayland_surface(handle: &impl HasWindowHandle) -> *mut wl_surface {
+    match handle.window_handle().unwrap().as_raw() {
+        RawWindowHandle::Wayland(handle) => handle.surface.as_ptr().cast(),
+        raw => panic!("unexpected window handle {raw:?}"),
+    }
+}
+
+#[test]
+fn handles_carry_the_display_and_surface() {
+    // The mock never dereferences these.
+    let display = without_provenance_mut::<wl_display>(0x1000);
+    let surface = without_provenance_mut::<wl_surface>(0x2000);
+    let context = unsafe { Context::new(display, |_, _| {}) }.unwrap();
+    let events = FrameEventQueue::new();
+    let frame = unsafe { context.decorate(surface, events.handler(640, 480)) }.unwrap();
+    let [mock_context] = contexts()[..] else {
+        panic!()
+    };
+    let [mock_frame] = frames()[..] else { panic!() };
+
+    assert_eq!(wayland_display(&context), mock_context.display());
+    assert_eq!(wayland_display(&frame), mock_context.display());
+    assert_eq!(wayland_display(&*frame), mock_context.display());
+    assert_eq!(wayland_surface(&frame), mock_frame.surface());
+    assert_eq!(wayland_surface(&*frame), mock_frame.surface());
+    assert_eq!(mock_context.display(), display);
+    assert_eq!(mock_frame.surface(), surface);
+}
+
+#[test]
+fn null_pointers_are_unavailable() {
+    let context = unsafe { Context::new(null_mut(), |_, _| {}) }.unwrap();
+    let events = FrameEventQueue::new();
+    let frame = unsafe { context.decorate(null_mut(), events.handler(640, 480)) }.unwrap();
+
+    let unavailable = |error| matches!(error, HandleError::Unavailable);
+    assert!(unavailable(context.display_handle().unwrap_err()));
+    assert!(unavailable(frame.display_handle().unwrap_err()));
+    assert!(unavailable(frame.window_handle().unwrap_err()));
+}
@@ -0,0 +1,98 @@
+use core::fmt;
+
+use super::UnknownValue;
+use crate::libdecor::*;
+
+/// A typed `libdecor_resize_edge`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResizeEdge {
+    None,
+    Top,
+    Bottom,
+    Left,
+    TopLeft,
+    BottomLeft,
+    Right,
+    TopRight,
+    BottomRight,
+}
+
+impl ResizeEdge {
+    /// Hit-tests a pointer position, in surface coordinates, against resize handles `border`
+    /// units thick along the inside edges of a `width` by `height` surface.
+    ///
+    /// Returns [`ResizeEdge::None`] if the position is outside the surface or away from its edges.
+    pub fn from_position(x: f64, y: f64, width: i32, height: i32, border: i32) -> Self {
+        let (width, height, border) = (f64::from(width), f64::from(height), f64::from(border));
+        if !(0.0..width).contains(&x) || !(0.0..height).contains(&y) {
+            return Self::None;
+        }
+
+        let left = x < border;
+        let right = x >= width - border;
+        let top = y < border;
+        let bottom = y >= height - border;
+        match (top, bottom, left, right) {
+            (true, _, true, _) => Self::TopLeft,
+            (true, _, _, true) => Self::TopRight,
+            (_, true, true, _) => Self::BottomLeft,
+            (_, true, _, true) => Self::BottomRight,
+            (true, _, _, _) => Self::Top,
+            (_, true, _, _) => Self::Bottom,
+            (_, _, true, _) => Self::Left,
+            (_, _, _, true) => Self::Right,
+            _ => Self::None,
+        }
+    }
+}
+
+impl TryFrom<libdecor_resize_edge> for ResizeEdge {
+    type Error = UnknownValue;
+
+    fn try_from(edge: libdecor_resize_edge) -> Result<Self, UnknownValue> {
+        match edge {
+            LIBDECOR_RESIZE_EDGE_NONE => Ok(Self::None),
+            LIBDECOR_RESIZE_EDGE_TOP => Ok(Self::Top),
+            LIBDECOR_RESIZE_EDGE_BOTTOM => Ok(Self::Bottom),
+            LIBDECOR_RESIZE_EDGE_LEFT => Ok(Self::Left),
+            LIBDECOR_RESIZE_EDGE_TOP_LEFT => Ok(Self::TopLeft),
+            LIBDECOR_RESIZE_EDGE_BOTTOM_LEFT => Ok(Self::BottomLeft),
+            LIBDECOR_RESIZE_EDGE_RIGHT => Ok(Self::Right),
+            LIBDECOR_RESIZE_EDGE_TOP_RIGHT => Ok(Self::TopRight),
+            LIBDECOR_RESIZE_EDGE_BOTTOM_RIGHT => Ok(Self::BottomRight),
+            _ => Err(UnknownValue(edge)),
+        }
+    }
+}
+
+impl From<ResizeEdge> for libdecor_resize_edge {
+    fn from(edge: ResizeEdge) -> Self {
+        match edge {
+            ResizeEdge::None => LIBDECOR_RESIZE_EDGE_NONE,
+            ResizeEdge::Top => LIBDECOR_RESIZE_EDGE_TOP,
+            ResizeEdge::Bottom => LIBDECOR_RESIZE_EDGE_BOTTOM,
+            ResizeEdge::Left => LIBDECOR_RESIZE_EDGE_LEFT,
+            ResizeEdge::TopLeft => LIBDECOR_RESIZE_EDGE_TOP_LEFT,
+            ResizeEdge::BottomLeft => LIBDECOR_RESIZE_EDGE_BOTTOM_LEFT,
+            ResizeEdge::Right => LIBDECOR_RESIZE_EDGE_RIGHT,
+            ResizeEdge::TopRight => LIBDECOR_RESIZE_EDGE_TOP_RIGHT,
+            ResizeEdge::BottomRight => LIBDECOR_RESIZE_EDGE_BOTTOM_RIGHT,
+        }
+    }
+}
+
+impl fmt::Display for ResizeEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Left => "left",
+            Self::TopLeft => "top left",
+            Self::BottomLeft => "bottom left",
+            Self::Right => "right",
+            Self::TopRight => "top right",
+            Self::BottomRight => "bottom right",
+        })
+    }
+}
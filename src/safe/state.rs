@@ -0,0 +1,51 @@
+use core::{error, ffi::c_int, fmt, ptr::NonNull};
+
+use crate::libdecor::*;
+
+/// An owned `*mut libdecor_state`, freed on drop.
+pub struct State {
+    raw: NonNull<libdecor_state>,
+}
+
+/// The error returned by [`State::new`] for a size that libdecor can't represent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl State {
+    /// Calls `libdecor_state_new`, rejecting sizes that aren't positive `c_int` values.
+    pub fn new(width: u32, height: u32) -> Result<Self, InvalidSize> {
+        let invalid = InvalidSize { width, height };
+        let content_width = c_int::try_from(width).map_err(|_| invalid)?;
+        let content_height = c_int::try_from(height).map_err(|_| invalid)?;
+        if content_width <= 0 || content_height <= 0 {
+            return Err(invalid);
+        }
+
+        emit!(trace, "libdecor_state_new", content_width, content_height);
+        let raw = unsafe { libdecor_state_new(content_width, content_height) };
+        let raw = NonNull::new(raw).expect("libdecor_state_new failed");
+        Ok(Self { raw })
+    }
+
+    pub fn as_raw(&self) -> *mut libdecor_state {
+        self.raw.as_ptr()
+    }
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        emit!(trace, "libdecor_state_free");
+        unsafe { libdecor_state_free(self.as_raw()) };
+    }
+}
+
+impl fmt::Display for InvalidSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid content size {}x{}", self.width, self.height)
+    }
+}
+
+impl error::Error for InvalidSize {}
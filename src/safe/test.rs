@@ -0,0 +1,658 @@
+use core::ffi::c_int;
+use std::{format, vec, vec::Vec};
+
+use crate::{
+    libdecor::*,
+    safe::{Capabilities, Error, InvalidSize, ResizeEdge, State, UnknownValue, WindowState},
+};
+
+#[test]
+fn resize_edge_from_position() {
+    let edge = |x, y| ResizeEdge::from_position(x, y, 100, 50, 5);
+    assert_eq!(edge(50.0, 25.0), ResizeEdge::None);
+    assert_eq!(edge(50.0, 0.0), ResizeEdge::Top);
+    assert_eq!(edge(50.0, 49.5), ResizeEdge::Bottom);
+    assert_eq!(edge(4.9, 25.0), ResizeEdge::Left);
+    assert_eq!(edge(95.0, 25.0), ResizeEdge::Right);
+    // Corners take precedence over the edges they join.
+    assert_eq!(edge(0.0, 0.0), ResizeEdge::TopLeft);
+    assert_eq!(edge(99.0, 4.0), ResizeEdge::TopRight);
+    assert_eq!(edge(4.0, 45.0), ResizeEdge::BottomLeft);
+    assert_eq!(edge(99.9, 49.9), ResizeEdge::BottomRight);
+    // Positions outside the surface, including on its far edges, aren't on a handle.
+    assert_eq!(edge(-0.1, 25.0), ResizeEdge::None);
+    assert_eq!(edge(100.0, 25.0), ResizeEdge::None);
+    assert_eq!(edge(50.0, -1.0), ResizeEdge::None);
+    assert_eq!(edge(50.0, 50.0), ResizeEdge::None);
+    assert_eq!(edge(f64::NAN, 25.0), ResizeEdge::None);
+}
+
+#[test]
+fn resize_edge_from_position_with_overlapping_borders() {
+    // With `border * 2 > width` the left and right handles overlap, and left wins, as does top
+    // over bottom.
+    let edge = |x, y| ResizeEdge::from_position(x, y, 10, 8, 6);
+    assert_eq!(edge(5.0, 3.0), ResizeEdge::TopLeft);
+    assert_eq!(edge(9.0, 3.0), ResizeEdge::TopRight);
+    assert_eq!(edge(5.0, 7.0), ResizeEdge::BottomLeft);
+    assert_eq!(edge(9.0, 7.0), ResizeEdge::BottomRight);
+    assert_eq!(
+        ResizeEdge::from_position(5.0, 20.0, 10, 40, 6),
+        ResizeEdge::Left
+    );
+    assert_eq!(
+        ResizeEdge::from_position(20.0, 5.0, 40, 10, 6),
+        ResizeEdge::Top
+    );
+}
+
+#[test]
+fn resize_edge_conversions() {
+    let edges = [
+        (ResizeEdge::None, LIBDECOR_RESIZE_EDGE_NONE),
+        (ResizeEdge::Top, LIBDECOR_RESIZE_EDGE_TOP),
+        (ResizeEdge::Bottom, LIBDECOR_RESIZE_EDGE_BOTTOM),
+        (ResizeEdge::Left, LIBDECOR_RESIZE_EDGE_LEFT),
+        (ResizeEdge::TopLeft, LIBDECOR_RESIZE_EDGE_TOP_LEFT),
+        (ResizeEdge::BottomLeft, LIBDECOR_RESIZE_EDGE_BOTTOM_LEFT),
+        (ResizeEdge::Right, LIBDECOR_RESIZE_EDGE_RIGHT),
+        (ResizeEdge::TopRight, LIBDECOR_RESIZE_EDGE_TOP_RIGHT),
+        (ResizeEdge::BottomRight, LIBDECOR_RESIZE_EDGE_BOTTOM_RIGHT),
+    ];
+    for (edge, raw) in edges {
+        assert_eq!(libdecor_resize_edge::from(edge), raw);
+        assert_eq!(ResizeEdge::try_from(raw), Ok(edge));
+    }
+    assert_eq!(ResizeEdge::try_from(9), Err(UnknownValue(9)));
+    assert_eq!(ResizeEdge::try_from(-1), Err(UnknownValue(-1)));
+}
+
+#[test]
+fn error_conversions() {
+    let errors = [
+        (
+            Error::CompositorIncompatible,
+            LIBDECOR_ERROR_COMPOSITOR_INCOMPATIBLE,
+        ),
+        (
+            Error::InvalidFrameConfiguration,
+            LIBDECOR_ERROR_INVALID_FRAME_CONFIGURATION,
+        ),
+    ];
+    for (error, raw) in errors {
+        assert_eq!(libdecor_error::from(error), raw);
+        assert_eq!(Error::try_from(raw), Ok(error));
+    }
+    assert_eq!(Error::try_from(2), Err(UnknownValue(2)));
+}
+
+#[test]
+fn flags_iter() {
+    assert_eq!(WindowState::empty().iter().count(), 0);
+    assert_eq!(
+        (WindowState::TILED_TOP | WindowState::ACTIVE)
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![WindowState::ACTIVE, WindowState::TILED_TOP],
+    );
+    // Unnamed bits are folded into a single final value.
+    assert_eq!(
+        Capabilities::from(LIBDECOR_ACTION_RESIZE | 1 << 5 | 1 << 9)
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![Capabilities::RESIZE, Capabilities::from(1 << 5 | 1 << 9)],
+    );
+}
+
+#[test]
+fn flags_debug() {
+    assert_eq!(format!("{:?}", WindowState::empty()), "WindowState(0x0)");
+    assert_eq!(
+        format!("{:?}", WindowState::MAXIMIZED | WindowState::FULLSCREEN),
+        "WindowState(MAXIMIZED | FULLSCREEN)",
+    );
+    assert_eq!(
+        format!("{:?}", Capabilities::from(1 << 5 | 1 << 9)),
+        "Capabilities(0x220)",
+    );
+    assert_eq!(
+        format!("{:?}", Capabilities::from(LIBDECOR_ACTION_CLOSE | 1 << 5)),
+        "Capabilities(CLOSE | 0x20)",
+    );
+}
+
+#[test]
+fn flags_suspended() {
+    let state = WindowState::from(LIBDECOR_WINDOW_STATE_ACTIVE | 1 << 7);
+    #[cfg(feature = "libdecor-0-2-0")]
+    {
+        assert_eq!(state, WindowState::ACTIVE | WindowState::SUSPENDED);
+        assert_eq!(format!("{state:?}"), "WindowState(ACTIVE | SUSPENDED)");
+    }
+    // Without libdecor 0.2.0 the bit has no name.
+    #[cfg(not(feature = "libdecor-0-2-0"))]
+    assert_eq!(format!("{state:?}"), "WindowState(ACTIVE | 0x80)");
+}
+
+#[test]
+fn state_rejects_invalid_sizes() {
+    let too_big = c_int::MAX as u32 + 1;
+    for (width, height) in [(0, 480), (640, 0), (0, 0), (too_big, 480), (640, too_big)] {
+        assert_eq!(
+            State::new(width, height).err(),
+            Some(InvalidSize { width, height }),
+        );
+    }
+}
+
+#[cfg(feature = "mock")]
+mod mock {
+    use core::{
+        cell::{Cell, RefCell},
+        ffi::{CStr, c_int},
+        time::Duration,
+    };
+    use std::{
+        any::Any,
+        boxed::Box,
+        ffi::CString,
+        panic::{AssertUnwindSafe, catch_unwind},
+        ptr::null_mut,
+        rc::Rc,
+        string::String,
+        vec,
+        vec::Vec,
+    };
+
+    use crate::{
+        libdecor::*,
+        mock::{Call, MockContext, contexts, frames},
+        safe::{
+            Capabilities, Configuration, Context, ContextEvent, Error, Event, EventQueue,
+            FrameEvent, FrameHandler, FrameRef, InvalidSize, State, WindowBuilder, WindowState,
+        },
+    };
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<&'static str>,
+        panic_on: Option<&'static str>,
+    }
+
+    impl Recorder {
+        fn panicking_on(event: &'static str) -> Self {
+            Self {
+                panic_on: Some(event),
+                ..Self::default()
+            }
+        }
+
+        fn record(&mut self, event: &'static str) {
+            self.events.push(event);
+            if self.panic_on == Some(event) {
+                panic!("{event} panicked");
+            }
+        }
+    }
+
+    impl FrameHandler for Recorder {
+        fn configure(&mut self, frame: &FrameRef, configuration: Configuration<'_>) {
+            self.record("configure");
+            configuration.commit(frame, 640, 480).unwrap();
+        }
+
+        fn close(&mut self, _frame: &FrameRef) {
+            self.record("close");
+        }
+
+        fn commit(&mut self, _frame: &FrameRef) {
+            self.record("commit");
+        }
+    }
+
+    fn context() -> Context {
+        unsafe { Context::new(null_mut(), |_, _| {}) }.unwrap()
+    }
+
+    fn panic_message(result: Result<impl Sized, Box<dyn Any + Send>>) -> String {
+        match result {
+            Ok(_) => panic!("expected a panic"),
+            Err(payload) => *payload.downcast::<String>().unwrap(),
+        }
+    }
+
+    #[test]
+    fn state_accepts_sizes_up_to_c_int_max() {
+        let max = c_int::MAX as u32;
+        assert!(State::new(1, 1).is_ok());
+        assert!(State::new(max, max).is_ok());
+    }
+
+    #[test]
+    fn dispatch_resumes_handler_panics() {
+        let context = context();
+        let frame =
+            unsafe { context.decorate(null_mut(), Recorder::panicking_on("configure")) }.unwrap();
+        let [mock_frame] = frames()[..] else { panic!() };
+        mock_frame.configure(None, None);
+        mock_frame.close();
+
+        let result = catch_unwind(AssertUnwindSafe(|| context.dispatch(None)));
+        assert_eq!(panic_message(result), "configure panicked");
+        // The rest of the dispatch is skipped.
+        assert_eq!(frame.handler().events, vec!["configure"]);
+
+        mock_frame.close();
+        assert_eq!(context.dispatch(None).unwrap(), 1);
+        assert_eq!(frame.handler().events, vec!["configure", "close"]);
+    }
+
+    #[test]
+    fn errors_reach_the_handler_outside_libdecor_calls() {
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let mock_context = Rc::new(Cell::new(None::<MockContext>));
+        let on_error = {
+            let (errors, mock_context) = (errors.clone(), mock_context.clone());
+            move |kind, message: &CStr| {
+                // The first error raises another from within the handler.
+                let first = errors.borrow().is_empty();
+                if let (true, Some(mock_context)) = (first, mock_context.get()) {
+                    mock_context.raise_error(LIBDECOR_ERROR_INVALID_FRAME_CONFIGURATION, c"nested");
+                }
+                errors.borrow_mut().push((kind, CString::from(message)));
+            }
+        };
+        let _context = unsafe { Context::new(null_mut(), on_error) }.unwrap();
+        let [context] = contexts()[..] else { panic!() };
+        mock_context.set(Some(context));
+
+        context.raise_error(LIBDECOR_ERROR_COMPOSITOR_INCOMPATIBLE, c"oops");
+        assert_eq!(
+            *errors.borrow(),
+            vec![
+                (Ok(Error::CompositorIncompatible), c"oops".into()),
+                (Ok(Error::InvalidFrameConfiguration), c"nested".into()),
+            ],
+        );
+    }
+
+    #[test]
+    fn error_handler_panics_resume_on_the_next_call() {
+        let context =
+            unsafe { Context::new(null_mut(), |_, _| panic!("on_error panicked")) }.unwrap();
+        let [mock_context] = contexts()[..] else {
+            panic!()
+        };
+
+        mock_context.raise_error(LIBDECOR_ERROR_COMPOSITOR_INCOMPATIBLE, c"oops");
+        let result = catch_unwind(AssertUnwindSafe(|| context.dispatch(None)));
+        assert_eq!(
+            *result.unwrap_err().downcast::<&str>().unwrap(),
+            "on_error panicked",
+        );
+        assert_eq!(context.dispatch(None).unwrap(), 0);
+    }
+
+    #[test]
+    fn frame_calls_resume_handler_panics() {
+        let context = context();
+        let frame =
+            unsafe { context.decorate(null_mut(), Recorder::panicking_on("close")) }.unwrap();
+
+        let result = catch_unwind(AssertUnwindSafe(|| frame.close()));
+        assert_eq!(panic_message(result), "close panicked");
+        assert_eq!(frame.handler().events, vec!["close"]);
+
+        frames()[0].commit();
+        assert_eq!(context.dispatch(None).unwrap(), 1);
+        assert_eq!(frame.handler().events, vec!["close", "commit"]);
+    }
+
+    #[test]
+    fn configuring_a_borrowed_handler_panics() {
+        let context = context();
+        let frame = unsafe { context.decorate(null_mut(), Recorder::default()) }.unwrap();
+        let [mock_frame] = frames()[..] else { panic!() };
+        mock_frame.configure(None, None);
+
+        let handler = frame.handler();
+        let result = catch_unwind(AssertUnwindSafe(|| context.dispatch(None)));
+        assert_eq!(
+            *result.unwrap_err().downcast::<&str>().unwrap(),
+            "frame configured while its handler was borrowed",
+        );
+        drop(handler);
+        assert!(frame.handler().events.is_empty());
+        assert!(mock_frame.calls().is_empty());
+    }
+
+    #[test]
+    fn window_builder_sets_properties_before_mapping() {
+        let context = context();
+        // Dispatching once lets libdecor finish initializing, so frames get an `xdg_toplevel`
+        // right away.
+        assert_eq!(context.dispatch(None).unwrap(), 0);
+        let _frame = unsafe {
+            WindowBuilder::new()
+                .title(c"title")
+                .app_id(c"app-id")
+                .min_content_size(320, 240)
+                .max_content_size(1280, 960)
+                .capabilities(Capabilities::MOVE | Capabilities::CLOSE)
+                .visible_decorations(false)
+                .maximized(true)
+                .fullscreen_on(null_mut())
+                .build(&context, null_mut(), Recorder::default())
+        }
+        .unwrap();
+        let [mock_frame] = frames()[..] else { panic!() };
+        assert_eq!(
+            mock_frame.calls(),
+            vec![
+                Call::SetAppId(c"app-id".into()),
+                Call::SetTitle(c"title".into()),
+                Call::SetMinContentSize(320, 240),
+                Call::SetMaxContentSize(1280, 960),
+                Call::UnsetCapabilities(
+                    (Capabilities::RESIZE | Capabilities::MINIMIZE | Capabilities::FULLSCREEN)
+                        .into()
+                ),
+                Call::SetCapabilities((Capabilities::MOVE | Capabilities::CLOSE).into()),
+                Call::SetVisibility(false),
+                Call::SetMaximized,
+                Call::SetFullscreen { output: null_mut() },
+                Call::Map,
+            ],
+        );
+    }
+
+    #[test]
+    fn window_builder_defers_window_state_until_configured() {
+        let context = context();
+        let _frame = unsafe {
+            WindowBuilder::new()
+                .title(c"title")
+                .maximized(true)
+                .fullscreen_on(null_mut())
+                .build(&context, null_mut(), Recorder::default())
+        }
+        .unwrap();
+        let [mock_frame] = frames()[..] else { panic!() };
+        assert_eq!(
+            mock_frame.take_calls(),
+            vec![Call::SetTitle(c"title".into()), Call::Map],
+        );
+
+        mock_frame.configure(None, None);
+        assert_eq!(context.dispatch(None).unwrap(), 1);
+        assert_eq!(
+            mock_frame.take_calls(),
+            vec![
+                Call::SetMaximized,
+                Call::SetFullscreen { output: null_mut() },
+                Call::Commit {
+                    width: 640,
+                    height: 480,
+                    configured: true,
+                },
+            ],
+        );
+
+        mock_frame.configure(None, None);
+        assert_eq!(context.dispatch(None).unwrap(), 1);
+        assert_eq!(
+            mock_frame.take_calls(),
+            vec![Call::Commit {
+                width: 640,
+                height: 480,
+                configured: true,
+            }],
+        );
+    }
+
+    #[test]
+    fn event_queue_commits_and_orders_events() {
+        let events = unsafe { EventQueue::new(null_mut()) }.unwrap();
+        let handler = events.queue().handler(640, 480);
+        let frame = unsafe { events.context().decorate(null_mut(), handler) }.unwrap();
+        let [mock_frame] = frames()[..] else { panic!() };
+        mock_frame.configure(None, Some(LIBDECOR_WINDOW_STATE_ACTIVE));
+        mock_frame.configure(Some((800, 600)), None);
+        mock_frame.close();
+        mock_frame
+            .context()
+            .error(LIBDECOR_ERROR_COMPOSITOR_INCOMPATIBLE, c"oops");
+
+        let polled = events.poll_events().unwrap();
+        // Configurations are committed from within the dispatch, before any event is drained.
+        assert_eq!(
+            mock_frame.calls(),
+            vec![
+                Call::Commit {
+                    width: 640,
+                    height: 480,
+                    configured: true,
+                },
+                Call::Commit {
+                    width: 800,
+                    height: 600,
+                    configured: true,
+                },
+            ],
+        );
+        assert_eq!(
+            polled.collect::<Vec<_>>(),
+            vec![
+                Event::Context(ContextEvent::Error {
+                    kind: Ok(Error::CompositorIncompatible),
+                    message: c"oops".into(),
+                }),
+                Event::Frame(
+                    frame.id(),
+                    FrameEvent::Configure {
+                        size: None,
+                        state: Some(WindowState::ACTIVE),
+                        committed: Ok((640, 480)),
+                    },
+                ),
+                Event::Frame(
+                    frame.id(),
+                    FrameEvent::Configure {
+                        size: Some((800, 600)),
+                        state: None,
+                        committed: Ok((800, 600)),
+                    },
+                ),
+                Event::Frame(frame.id(), FrameEvent::Close),
+            ],
+        );
+    }
+
+    #[test]
+    fn event_queue_reports_failed_commits() {
+        let events = unsafe { EventQueue::new(null_mut()) }.unwrap();
+        let handler = events.queue().handler(0, 0);
+        let frame = unsafe { events.context().decorate(null_mut(), handler) }.unwrap();
+        let [mock_frame] = frames()[..] else { panic!() };
+
+        mock_frame.configure(None, None);
+        assert_eq!(
+            events.poll_events().unwrap().collect::<Vec<_>>(),
+            vec![Event::Frame(
+                frame.id(),
+                FrameEvent::Configure {
+                    size: None,
+                    state: None,
+                    committed: Err(InvalidSize {
+                        width: 0,
+                        height: 0,
+                    }),
+                },
+            )],
+        );
+        assert!(mock_frame.take_calls().is_empty());
+
+        mock_frame.configure(Some((800, 600)), None);
+        assert_eq!(
+            events.poll_events().unwrap().collect::<Vec<_>>(),
+            vec![Event::Frame(
+                frame.id(),
+                FrameEvent::Configure {
+                    size: Some((800, 600)),
+                    state: None,
+                    committed: Ok((800, 600)),
+                },
+            )],
+        );
+        assert_eq!(
+            mock_frame.take_calls(),
+            vec![Call::Commit {
+                width: 800,
+                height: 600,
+                configured: true,
+            }],
+        );
+    }
+
+    #[test]
+    fn event_queue_waits_only_when_empty() {
+        let events = unsafe { EventQueue::new(null_mut()) }.unwrap();
+        let handler = events.queue().handler(640, 480);
+        let frame = unsafe { events.context().decorate(null_mut(), handler) }.unwrap();
+        let [mock_frame] = frames()[..] else { panic!() };
+        let mock_context = mock_frame.context();
+
+        mock_frame.commit();
+        mock_frame.close();
+        assert_eq!(
+            events.poll_events().unwrap().next(),
+            Some(Event::Frame(frame.id(), FrameEvent::Commit)),
+        );
+        assert_eq!(mock_context.last_timeout(), Some(0));
+
+        // The close is still queued, so this doesn't wait.
+        assert_eq!(
+            events.wait_events(None).unwrap().collect::<Vec<_>>(),
+            vec![Event::Frame(frame.id(), FrameEvent::Close)],
+        );
+        assert_eq!(mock_context.last_timeout(), Some(0));
+
+        assert_eq!(events.wait_events(None).unwrap().count(), 0);
+        assert_eq!(mock_context.last_timeout(), Some(-1));
+        let timeout = Some(Duration::from_millis(5));
+        assert_eq!(events.wait_events(timeout).unwrap().count(), 0);
+        assert_eq!(mock_context.last_timeout(), Some(5));
+    }
+
+    #[cfg(feature = "weak")]
+    #[test]
+    fn content_size_getters_fall_back_without_weak_symbols() {
+        let context = context();
+        let frame = unsafe { context.decorate(null_mut(), Recorder::default()) }.unwrap();
+        frame.set_min_content_size(320, 240);
+        frame.set_max_content_size(1280, 960);
+        // Bypassing the wrappers leaves only libdecor with these sizes.
+        unsafe { libdecor_frame_set_min_content_size(frame.as_raw(), 160, 120) };
+        unsafe { libdecor_frame_set_max_content_size(frame.as_raw(), 2560, 1920) };
+
+        #[cfg(feature = "libdecor-0-1-1")]
+        {
+            assert_eq!(frame.min_content_size(), (160, 120));
+            assert_eq!(frame.max_content_size(), (2560, 1920));
+        }
+        crate::weak::hidden(|| {
+            assert_eq!(frame.min_content_size(), (320, 240));
+            assert_eq!(frame.max_content_size(), (1280, 960));
+        });
+    }
+
+    /// Records the message of every `tracing` event.
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct Messages(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    #[cfg(feature = "tracing")]
+    impl Messages {
+        fn take(&self) -> Vec<String> {
+            core::mem::take(&mut self.0.lock().unwrap())
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for Messages {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            struct Visitor<'a>(&'a mut Vec<String>);
+
+            impl tracing::field::Visit for Visitor<'_> {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn core::fmt::Debug,
+                ) {
+                    if field.name() == "message" {
+                        self.0.push(std::format!("{value:?}"));
+                    }
+                }
+            }
+
+            event.record(&mut Visitor(&mut self.0.lock().unwrap()));
+        }
+
+        fn enter(&self, _span: &tracing::span::Id) {}
+
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn wrappers_trace_libdecor_calls() {
+        let messages = Messages::default();
+        tracing::subscriber::with_default(messages.clone(), || {
+            let context = context();
+            let frame = unsafe { context.decorate(null_mut(), Recorder::default()) }.unwrap();
+            assert_eq!(messages.take(), ["libdecor_new", "libdecor_decorate"]);
+
+            frame.set_title(c"title");
+            frame.title();
+            frame.is_visible();
+            frame.has_capability(Capabilities::MOVE);
+            frame.translate_coordinate(1, 2);
+            frame.is_floating();
+            frame.xdg_surface();
+            frame.xdg_toplevel();
+            assert_eq!(
+                messages.take(),
+                [
+                    "libdecor_frame_set_title",
+                    "libdecor_frame_get_title",
+                    "libdecor_frame_is_visible",
+                    "libdecor_frame_has_capability",
+                    "libdecor_frame_translate_coordinate",
+                    "libdecor_frame_is_floating",
+                    "libdecor_frame_get_xdg_surface",
+                    "libdecor_frame_get_xdg_toplevel",
+                ],
+            );
+
+            frames()[0].close();
+            assert_eq!(context.dispatch(None).unwrap(), 1);
+            assert_eq!(messages.take(), ["libdecor_dispatch", "close"]);
+
+            drop(frame);
+            drop(context);
+            assert_eq!(messages.take(), ["libdecor_frame_unref", "libdecor_unref"]);
+        });
+    }
+}
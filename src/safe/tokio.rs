@@ -0,0 +1,96 @@
+use core::{
+    ops::Deref,
+    pin::Pin,
+    task::{Context as TaskContext, Poll, ready},
+    time::Duration,
+};
+use std::io;
+
+use ::futures_core::Stream;
+use ::tokio::io::{Interest, unix::AsyncFd};
+
+use super::{Context, FrameEvent, FrameEventQueue, FrameId};
+
+#[cfg(all(test, feature = "mock"))]
+mod test;
+
+/// A [`Context`] registered with the current tokio runtime.
+pub struct AsyncContext {
+    fd: AsyncFd<Context>,
+}
+
+/// A [`Stream`] of the events pushed onto a [`FrameEventQueue`], dispatching the context as
+/// needed to produce them.
+pub struct FrameEventStream<'a> {
+    context: &'a AsyncContext,
+    queue: FrameEventQueue,
+}
+
+impl AsyncContext {
+    /// Registers `context`'s `libdecor_get_fd` with the current tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn new(context: Context) -> io::Result<Self> {
+        // The fd belongs to the context's `wl_display`, which outlives it.
+        let fd = unsafe { AsyncFd::register_with_interest(context, Interest::READABLE)? };
+        Ok(Self { fd })
+    }
+
+    pub fn into_inner(self) -> Context {
+        self.fd.into_inner()
+    }
+
+    /// Waits until the context's fd is readable and dispatches at least one event.
+    pub async fn dispatch_pending(&self) -> io::Result<usize> {
+        loop {
+            let count = self.dispatch(Some(Duration::ZERO))?;
+            if count > 0 {
+                return Ok(count);
+            }
+            self.fd.readable().await?.clear_ready();
+        }
+    }
+
+    /// Returns a stream of the events pushed onto `queue`.
+    pub fn events(&self, queue: FrameEventQueue) -> FrameEventStream<'_> {
+        FrameEventStream {
+            context: self,
+            queue,
+        }
+    }
+}
+
+impl Deref for AsyncContext {
+    type Target = Context;
+
+    fn deref(&self) -> &Context {
+        self.fd.get_ref()
+    }
+}
+
+impl Stream for FrameEventStream<'_> {
+    type Item = io::Result<(FrameId, FrameEvent)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(event) = self.queue.pop() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            if let Err(err) = self.context.dispatch(Some(Duration::ZERO)) {
+                return Poll::Ready(Some(Err(err)));
+            }
+            if !self.queue.is_empty() {
+                continue;
+            }
+
+            // Another stream dispatching the same context may fill this queue, too.
+            self.queue.register_waker(cx.waker());
+            match ready!(self.context.fd.poll_read_ready(cx)) {
+                Ok(mut guard) => guard.clear_ready(),
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use core::{future::poll_fn, pin::Pin, task::Poll};
+use std::{io, ptr::null_mut};
+
+use ::futures_core::Stream;
+
+use super::*;
+use crate::mock::frames;
+
+async fn next(events: &mut FrameEventStream<'_>) -> Option<io::Result<(FrameId, FrameEvent)>> {
+    poll_fn(|cx| Pin::new(&mut *events).poll_next(cx)).await
+}
+
+#[::tokio::test]
+async fn stream_yields_queued_events_then_pends() {
+    let context = unsafe { Context::new(null_mut(), |_, _| {}) }.unwrap();
+    let context = AsyncContext::new(context).unwrap();
+    let queue = FrameEventQueue::new();
+    let frame = unsafe { context.decorate(null_mut(), queue.handler(640, 480)) }.unwrap();
+    let [mock_frame] = frames()[..] else { panic!() };
+    let mut events = context.events(queue);
+
+    mock_frame.configure(Some((800, 600)), None);
+    mock_frame.close();
+    assert_eq!(
+        next(&mut events).await.unwrap().unwrap(),
+        (
+            frame.id(),
+            FrameEvent::Configure {
+                size: Some((800, 600)),
+                state: None,
+                committed: Ok((800, 600)),
+            },
+        ),
+    );
+    assert_eq!(
+        next(&mut events).await.unwrap().unwrap(),
+        (frame.id(), FrameEvent::Close),
+    );
+
+    // With nothing queued, the stream waits for the fd to become readable again.
+    let poll = poll_fn(|cx| Poll::Ready(Pin::new(&mut events).poll_next(cx))).await;
+    assert!(poll.is_pending());
+
+    mock_frame.commit();
+    assert_eq!(
+        next(&mut events).await.unwrap().unwrap(),
+        (frame.id(), FrameEvent::Commit),
+    );
+}
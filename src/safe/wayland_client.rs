@@ -0,0 +1,89 @@
+use core::ffi::{CStr, c_void};
+use std::io;
+
+use ::wayland_backend::client::ObjectId;
+use ::wayland_client::{Connection, Proxy, protocol::wl_surface::WlSurface};
+use ::wayland_protocols::xdg::shell::client::{xdg_surface::XdgSurface, xdg_toplevel::XdgToplevel};
+
+use super::{Context, Error, Frame, FrameHandler, FrameRef, UnknownValue};
+
+// Wayland-rs needs a live connection, which the headless compositor provides.
+#[cfg(all(test, feature = "mock", feature = "testing"))]
+mod test;
+
+impl Context {
+    /// Calls `libdecor_new` on `connection`'s `wl_display`.
+    ///
+    /// # Safety
+    ///
+    /// `connection` must outlive the returned context. Note that a [`Connection`] created with
+    /// [`Connection::from_backend`] on a foreign display doesn't keep that display alive.
+    pub unsafe fn from_connection(
+        connection: &Connection,
+        on_error: impl FnMut(Result<Error, UnknownValue>, &CStr) + 'static,
+    ) -> io::Result<Self> {
+        let display = connection.backend().display_ptr();
+        unsafe { Self::new(display.cast(), on_error) }
+    }
+
+    /// Calls `libdecor_decorate` on a wayland-rs surface, routing the frame's callbacks to
+    /// `handler`.
+    ///
+    /// Fails if `surface` was already destroyed or belongs to another connection.
+    ///
+    /// # Safety
+    ///
+    /// `surface` must not be destroyed before the returned frame.
+    pub unsafe fn decorate_surface<H: FrameHandler + 'static>(
+        &self,
+        surface: &WlSurface,
+        handler: H,
+    ) -> io::Result<Frame<'_, H>> {
+        let backend = surface.backend().upgrade();
+        if backend.is_none_or(|backend| backend.display_ptr().cast() != self.display()) {
+            let message = "surface belongs to another wl_display";
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+        let ptr = surface.id().as_ptr();
+        if ptr.is_null() {
+            let message = "surface was already destroyed";
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+        unsafe { self.decorate(ptr.cast(), handler) }
+    }
+}
+
+impl FrameRef {
+    /// Wraps [`FrameRef::xdg_surface`] in a wayland-rs proxy for sending requests.
+    ///
+    /// Events keep going to libdecor. Returns `None` if there's no `xdg_surface` yet.
+    ///
+    /// # Safety
+    ///
+    /// `connection` must be the one the frame's context was created on, and the proxy, and any
+    /// clone of it, must not be used once the frame is freed.
+    pub unsafe fn xdg_surface_proxy(&self, connection: &Connection) -> Option<XdgSurface> {
+        unsafe { proxy(connection, self.xdg_surface().cast()) }
+    }
+
+    /// Wraps [`FrameRef::xdg_toplevel`] in a wayland-rs proxy for sending requests.
+    ///
+    /// Events keep going to libdecor. Returns `None` if there's no `xdg_toplevel` yet.
+    ///
+    /// # Safety
+    ///
+    /// `connection` must be the one the frame's context was created on, and the proxy, and any
+    /// clone of it, must not be used once the frame is freed.
+    pub unsafe fn xdg_toplevel_proxy(&self, connection: &Connection) -> Option<XdgToplevel> {
+        unsafe { proxy(connection, self.xdg_toplevel().cast()) }
+    }
+}
+
+/// Wraps a `wl_proxy` that wayland-rs doesn't manage.
+unsafe fn proxy<P: Proxy>(connection: &Connection, ptr: *mut c_void) -> Option<P> {
+    if ptr.is_null() {
+        return None;
+    }
+    let id = unsafe { ObjectId::from_ptr(P::interface(), ptr.cast()) }.ok()?;
+    P::from_id(connection, id).ok()
+}
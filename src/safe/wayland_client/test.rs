@@ -0,0 +1,105 @@
+use std::{io::ErrorKind, os::unix::net::UnixStream};
+
+use ::wayland_client::{
+    Connection, Dispatch, EventQueue, QueueHandle, delegate_noop,
+    globals::{GlobalListContents, registry_queue_init},
+    protocol::{wl_compositor::WlCompositor, wl_registry::WlRegistry},
+};
+use ::wayland_protocols::xdg::shell::client::xdg_wm_base::XdgWmBase;
+
+use super::*;
+use crate::{
+    mock::{contexts, frames},
+    safe::FrameEventQueue,
+    testing::HeadlessCompositor,
+};
+
+struct State;
+
+impl Dispatch<WlRegistry, GlobalListContents> for State {
+    fn event(
+        _state: &mut Self,
+        _registry: &WlRegistry,
+        _event: <WlRegistry as Proxy>::Event,
+        _data: &GlobalListContents,
+        _connection: &Connection,
+        _queue: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+delegate_noop!(State: WlCompositor);
+delegate_noop!(State: ignore WlSurface);
+delegate_noop!(State: ignore XdgWmBase);
+delegate_noop!(State: ignore XdgSurface);
+delegate_noop!(State: ignore XdgToplevel);
+
+/// Connects to `compositor` and binds its `wl_compositor` and `xdg_wm_base`.
+fn connect(
+    compositor: &HeadlessCompositor,
+) -> (Connection, EventQueue<State>, WlCompositor, XdgWmBase) {
+    let socket = UnixStream::from(compositor.connect().unwrap());
+    let connection = Connection::from_socket(socket).unwrap();
+    let (globals, queue) = registry_queue_init::<State>(&connection).unwrap();
+    let wl_compositor = globals.bind(&queue.handle(), 4..=4, ()).unwrap();
+    let xdg_wm_base = globals.bind(&queue.handle(), 1..=1, ()).unwrap();
+    (connection, queue, wl_compositor, xdg_wm_base)
+}
+
+#[test]
+fn wayland_rs_objects_round_trip() {
+    let compositor = HeadlessCompositor::new().unwrap();
+    let (connection, mut queue, wl_compositor, xdg_wm_base) = connect(&compositor);
+    let qh = queue.handle();
+    let surface = wl_compositor.create_surface(&qh, ());
+    let xdg_surface = xdg_wm_base.get_xdg_surface(&surface, &qh, ());
+    let xdg_toplevel = xdg_surface.get_toplevel(&qh, ());
+    queue.roundtrip(&mut State).unwrap();
+
+    let context = unsafe { Context::from_connection(&connection, |_, _| {}) }.unwrap();
+    let [mock_context] = contexts()[..] else {
+        panic!()
+    };
+    let display = connection.backend().display_ptr();
+    assert_eq!(context.display(), display.cast());
+    assert_eq!(mock_context.display(), display.cast());
+
+    let events = FrameEventQueue::new();
+    let frame = unsafe { context.decorate_surface(&surface, events.handler(640, 480)) }.unwrap();
+    let [mock_frame] = frames()[..] else { panic!() };
+    assert_eq!(frame.surface(), surface.id().as_ptr().cast());
+    assert_eq!(mock_frame.surface(), surface.id().as_ptr().cast());
+
+    // libdecor creates the frame's shell objects once the context has been dispatched.
+    assert_eq!(unsafe { frame.xdg_surface_proxy(&connection) }, None);
+    assert_eq!(unsafe { frame.xdg_toplevel_proxy(&connection) }, None);
+    mock_frame.set_shell_objects(
+        xdg_surface.id().as_ptr().cast(),
+        xdg_toplevel.id().as_ptr().cast(),
+    );
+    context.dispatch(None).unwrap();
+    let xdg_surface_proxy = unsafe { frame.xdg_surface_proxy(&connection) }.unwrap();
+    let xdg_toplevel_proxy = unsafe { frame.xdg_toplevel_proxy(&connection) }.unwrap();
+    assert_eq!(xdg_surface_proxy, xdg_surface);
+    assert_eq!(xdg_toplevel_proxy, xdg_toplevel);
+    assert_eq!(xdg_toplevel_proxy.id().as_ptr(), xdg_toplevel.id().as_ptr());
+}
+
+#[test]
+fn decorate_surface_rejects_foreign_and_destroyed_surfaces() {
+    let compositor = HeadlessCompositor::new().unwrap();
+    let (connection, queue, wl_compositor, _) = connect(&compositor);
+    let (_other, other_queue, other_compositor, _) = connect(&compositor);
+    let context = unsafe { Context::from_connection(&connection, |_, _| {}) }.unwrap();
+    let events = FrameEventQueue::new();
+
+    let foreign = other_compositor.create_surface(&other_queue.handle(), ());
+    let result = unsafe { context.decorate_surface(&foreign, events.handler(640, 480)) };
+    assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidInput);
+
+    let destroyed = wl_compositor.create_surface(&queue.handle(), ());
+    destroyed.destroy();
+    let result = unsafe { context.decorate_surface(&destroyed, events.handler(640, 480)) };
+    assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidInput);
+    assert!(frames().is_empty());
+}
@@ -0,0 +1,145 @@
+use core::ffi::CStr;
+use std::io;
+
+use wayland_headers::wayland_client::{wl_output, wl_surface};
+
+use super::{Capabilities, Context, Frame, FrameHandler, FrameRef};
+
+/// Decorates a surface and maps it with its properties already set.
+///
+/// Properties are set before `libdecor_frame_map`. The maximized and fullscreen states are too if
+/// the frame already has an `xdg_toplevel`, and otherwise wait for its first configure, since
+/// libdecor only creates the `xdg_toplevel` once the context has finished initializing.
+#[derive(Clone, Copy)]
+pub struct WindowBuilder<'a> {
+    title: Option<&'a CStr>,
+    app_id: Option<&'a CStr>,
+    min_content_size: Option<(i32, i32)>,
+    max_content_size: Option<(i32, i32)>,
+    capabilities: Option<Capabilities>,
+    parent: Option<&'a FrameRef>,
+    fullscreen: Option<*mut wl_output>,
+    maximized: bool,
+    visible_decorations: bool,
+}
+
+impl Default for WindowBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> WindowBuilder<'a> {
+    pub const fn new() -> Self {
+        Self {
+            title: None,
+            app_id: None,
+            min_content_size: None,
+            max_content_size: None,
+            capabilities: None,
+            parent: None,
+            fullscreen: None,
+            maximized: false,
+            visible_decorations: true,
+        }
+    }
+
+    pub fn title(mut self, title: &'a CStr) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    pub fn app_id(mut self, app_id: &'a CStr) -> Self {
+        self.app_id = Some(app_id);
+        self
+    }
+
+    pub fn min_content_size(mut self, content_width: i32, content_height: i32) -> Self {
+        self.min_content_size = Some((content_width, content_height));
+        self
+    }
+
+    pub fn max_content_size(mut self, content_width: i32, content_height: i32) -> Self {
+        self.max_content_size = Some((content_width, content_height));
+        self
+    }
+
+    /// Leaves the frame with exactly `capabilities`, rather than libdecor's default of all of
+    /// them.
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    pub fn parent(mut self, parent: &'a FrameRef) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Requests fullscreen on `output`, or on an output of the compositor's choosing if null.
+    pub fn fullscreen_on(mut self, output: *mut wl_output) -> Self {
+        self.fullscreen = Some(output);
+        self
+    }
+
+    pub fn maximized(mut self, maximized: bool) -> Self {
+        self.maximized = maximized;
+        self
+    }
+
+    /// Whether libdecor draws decorations, `true` by default.
+    pub fn visible_decorations(mut self, visible_decorations: bool) -> Self {
+        self.visible_decorations = visible_decorations;
+        self
+    }
+
+    /// Decorates `surface` with [`Context::decorate`], sets the configured properties and maps
+    /// the frame.
+    ///
+    /// # Safety
+    ///
+    /// `surface` must be a valid `wl_surface` that outlives the returned frame, and the output
+    /// passed to [`WindowBuilder::fullscreen_on`], if any, must be null or a valid `wl_output`
+    /// until the frame is first configured.
+    pub unsafe fn build<'ctx, H: FrameHandler + 'static>(
+        self,
+        context: &'ctx Context,
+        surface: *mut wl_surface,
+        handler: H,
+    ) -> io::Result<Frame<'ctx, H>> {
+        let frame = unsafe { context.decorate(surface, handler) }?;
+        if let Some(app_id) = self.app_id {
+            frame.set_app_id(app_id);
+        }
+        if let Some(title) = self.title {
+            frame.set_title(title);
+        }
+        if let Some(parent) = self.parent {
+            frame.set_parent(Some(parent));
+        }
+        if let Some((content_width, content_height)) = self.min_content_size {
+            frame.set_min_content_size(content_width, content_height);
+        }
+        if let Some((content_width, content_height)) = self.max_content_size {
+            frame.set_max_content_size(content_width, content_height);
+        }
+        if let Some(capabilities) = self.capabilities {
+            let mut unset = Capabilities::MOVE
+                | Capabilities::RESIZE
+                | Capabilities::MINIMIZE
+                | Capabilities::FULLSCREEN
+                | Capabilities::CLOSE;
+            unset.remove(capabilities);
+            frame.unset_capabilities(unset);
+            frame.set_capabilities(capabilities);
+        }
+        if !self.visible_decorations {
+            frame.set_visibility(false);
+        }
+        if self.maximized || self.fullscreen.is_some() {
+            unsafe { frame.request_window_state(self.maximized, self.fullscreen) };
+        }
+        frame.map();
+        Ok(frame)
+    }
+}
@@ -1,13 +1,14 @@
 use std::{
     cell::{Cell, RefCell},
     ffi::{CStr, c_char, c_void},
-    os::raw::c_int,
-    ptr::{self, null},
+    os::{fd::IntoRawFd, raw::c_int},
+    ptr,
+    time::{Duration, Instant},
 };
 
 use wayland_headers::{wayland_client::*, xdg_shell_client_protocol::*};
 
-use crate::libdecor::*;
+use crate::{libdecor::*, testing::HeadlessCompositor};
 
 #[link(name = "wayland-client")]
 unsafe extern "C" {}
@@ -25,7 +26,10 @@ struct Globals {
     wl_compositor: Cell<*mut wl_compositor>,
     wl_shm: Cell<*mut wl_shm>,
     xdg_wm_base: Cell<*mut xdg_wm_base>,
+    // Boxed so the listeners' user data stays put as these grow.
+    #[allow(clippy::vec_box)]
     seats: RefCell<Vec<Box<Seat>>>,
+    #[allow(clippy::vec_box)]
     outputs: RefCell<Vec<Box<Output>>>,
 
     has_xrgb8888: Cell<bool>,
@@ -63,6 +67,7 @@ struct Window {
     wl_surface: Cell<*mut wl_surface>,
     scale_factor: Cell<i32>,
     frame: Cell<*mut libdecor_frame>,
+    content_size: Cell<(c_int, c_int)>,
 }
 
 impl Window {
@@ -79,7 +84,9 @@ fn test() {
     let globals = &globals;
     let globals_user_data = ptr::from_ref(globals).cast_mut().cast::<c_void>();
 
-    let wl_display = unsafe { wl_display_connect(null()) };
+    let compositor = HeadlessCompositor::new().unwrap();
+    let fd = compositor.connect().unwrap();
+    let wl_display = unsafe { wl_display_connect_to_fd(fd.into_raw_fd()) };
     assert!(!wl_display.is_null());
 
     let wl_registry = unsafe { wl_display_get_registry(wl_display) };
@@ -131,10 +138,37 @@ fn test() {
         libdecor_frame_set_min_content_size(frame, 15 * CHK, 10 * CHK);
     }
 
-    loop {
-        if unsafe { libdecor_dispatch(libdecor, -1) } < 0 {
-            break;
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !compositor
+        .toplevels()
+        .first()
+        .is_some_and(|toplevel| toplevel.acked)
+    {
+        assert!(
+            Instant::now() < deadline,
+            "timed out waiting for the compositor"
+        );
+        assert!(unsafe { libdecor_dispatch(libdecor, 100) } >= 0);
+    }
+
+    let toplevel = compositor.toplevels()[0].clone();
+    assert_eq!(toplevel.app_id.as_deref(), Some(c"libdecor-demo"));
+    assert_eq!(toplevel.title.as_deref(), Some(c"libdecor-demo"));
+    // The decorations add to the minimum window geometry.
+    assert!(toplevel.min_size.0 >= 15 * CHK && toplevel.min_size.1 >= 10 * CHK);
+    assert_eq!(window.content_size.get(), (20 * CHK, 15 * CHK));
+
+    unsafe {
+        libdecor_frame_unref(frame);
+        libdecor_unref(libdecor);
+        wl_surface_destroy(wl_surface);
+        for seat in globals.seats.take() {
+            wl_seat_destroy(seat.wl_seat);
+        }
+        for output in globals.outputs.take() {
+            wl_output_destroy(output.wl_output);
         }
+        wl_display_disconnect(wl_display);
     }
 }
 
@@ -368,10 +402,22 @@ static mut LIBDECOR_FRAME_INTERFACE: libdecor_frame_interface = libdecor_frame_i
 };
 
 unsafe extern "C" fn on_libdecor_frame_configure(
-    _frame: *mut libdecor_frame,
-    _configuration: *mut libdecor_configuration,
-    _window_user_data: *mut c_void,
+    frame: *mut libdecor_frame,
+    configuration: *mut libdecor_configuration,
+    window_user_data: *mut c_void,
 ) {
+    let window = Window::from_user_data(window_user_data);
+
+    let (mut width, mut height) = (20 * CHK, 15 * CHK);
+    unsafe {
+        libdecor_configuration_get_content_size(configuration, frame, &mut width, &mut height)
+    };
+    let state = unsafe { libdecor_state_new(width, height) };
+    unsafe {
+        libdecor_frame_commit(frame, state, configuration);
+        libdecor_state_free(state);
+    }
+    window.content_size.set((width, height));
 }
 
 unsafe extern "C" fn on_libdecor_frame_close(
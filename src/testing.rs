@@ -0,0 +1,676 @@
+//! A headless Wayland compositor for end-to-end tests of libdecor clients.
+//!
+//! [`HeadlessCompositor`] runs a minimal libwayland-server compositor on a background thread. It
+//! offers `wl_compositor`, `wl_subcompositor`, `wl_shm` and `xdg_wm_base` without drawing anything,
+//! records what each `xdg_toplevel` asks for, and lets the test send configure and close events.
+//! Clients connect through [`HeadlessCompositor::connect`] rather than `WAYLAND_DISPLAY`, so tests
+//! can run in parallel.
+
+use core::{
+    ffi::{CStr, c_int, c_void},
+    mem::size_of_val,
+    ptr::{NonNull, null_mut},
+};
+use std::{
+    boxed::Box,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::CString,
+    io::{self, Read, Write},
+    os::{
+        fd::{AsRawFd, IntoRawFd, OwnedFd},
+        unix::net::UnixStream,
+    },
+    sync::{
+        Arc, Mutex, MutexGuard,
+        mpsc::{self, Receiver, Sender},
+    },
+    thread::{self, JoinHandle},
+    vec::Vec,
+};
+
+use wayland_headers::{wayland_server::*, xdg_shell_server_protocol::*};
+
+// The mock's symbols would shadow the real libdecor this test runs against.
+#[cfg(all(test, not(feature = "mock")))]
+mod test;
+
+#[link(name = "wayland-server")]
+unsafe extern "C" {}
+
+#[link(name = "wayland-protocol-statics")]
+unsafe extern "C" {}
+
+/// The size of the pretend output, used when maximizing or fullscreening a toplevel.
+pub const OUTPUT_SIZE: (i32, i32) = (1920, 1080);
+
+/// A headless compositor running on a background thread, stopped when dropped.
+pub struct HeadlessCompositor {
+    shared: Arc<Mutex<Vec<Toplevel>>>,
+    commands: Sender<Command>,
+    wake: UnixStream,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Identifies an `xdg_toplevel` for the lifetime of a [`HeadlessCompositor`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ToplevelId(u64);
+
+/// What the compositor knows about an `xdg_toplevel`.
+#[derive(Clone, Debug)]
+pub struct Toplevel {
+    pub id: ToplevelId,
+    pub title: Option<CString>,
+    pub app_id: Option<CString>,
+    pub min_size: (i32, i32),
+    pub max_size: (i32, i32),
+    /// The window geometry of the last commit.
+    pub geometry: Option<(i32, i32, i32, i32)>,
+    /// The size and `xdg_toplevel_state`s of the last configure sent.
+    pub configured: Option<Configure>,
+    /// Whether the client acknowledged the last configure sent.
+    pub acked: bool,
+    /// Every request besides the properties above, in order.
+    pub requests: Vec<ToplevelRequest>,
+}
+
+/// An `xdg_toplevel.configure` event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Configure {
+    pub width: i32,
+    pub height: i32,
+    pub states: Vec<u32>,
+}
+
+impl Configure {
+    pub fn has_state(&self, state: u32) -> bool {
+        self.states.contains(&state)
+    }
+}
+
+/// An `xdg_toplevel` request recorded in [`Toplevel::requests`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ToplevelRequest {
+    Move { serial: u32 },
+    Resize { serial: u32, edges: u32 },
+    SetFullscreen,
+    SetMaximized,
+    SetMinimized,
+    SetParent,
+    ShowWindowMenu { serial: u32, x: i32, y: i32 },
+    UnsetFullscreen,
+    UnsetMaximized,
+}
+
+enum Command {
+    Connect(OwnedFd),
+    Configure(ToplevelId, Configure),
+    Close(ToplevelId),
+    Quit,
+}
+
+impl HeadlessCompositor {
+    /// Starts the compositor thread.
+    pub fn new() -> io::Result<Self> {
+        let shared = Arc::new(Mutex::new(Vec::new()));
+        let (commands, receiver) = mpsc::channel();
+        let (wake, wake_reader) = UnixStream::pair()?;
+        wake_reader.set_nonblocking(true)?;
+
+        let (ready, started) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("headless-compositor".into())
+            .spawn({
+                let shared = shared.clone();
+                move || unsafe { run(shared, receiver, wake_reader, ready) }
+            })?;
+        started.recv().unwrap()?;
+
+        Ok(Self {
+            shared,
+            commands,
+            wake,
+            thread: Some(thread),
+        })
+    }
+
+    /// Connects a new client, returning its end of the connection for
+    /// `wl_display_connect_to_fd`.
+    pub fn connect(&self) -> io::Result<OwnedFd> {
+        let (client, server) = UnixStream::pair()?;
+        self.send(Command::Connect(server.into()));
+        Ok(client.into())
+    }
+
+    /// The live toplevels, oldest first.
+    pub fn toplevels(&self) -> Vec<Toplevel> {
+        self.lock().clone()
+    }
+
+    /// The toplevel identified by `id`, if it's still alive.
+    pub fn toplevel(&self, id: ToplevelId) -> Option<Toplevel> {
+        self.lock()
+            .iter()
+            .find(|toplevel| toplevel.id == id)
+            .cloned()
+    }
+
+    /// Sends a configure event with the given size and `xdg_toplevel_state`s.
+    pub fn configure(&self, id: ToplevelId, width: i32, height: i32, states: &[u32]) {
+        let configure = Configure {
+            width,
+            height,
+            states: states.into(),
+        };
+        self.send(Command::Configure(id, configure));
+    }
+
+    /// Configures the toplevel as maximized on the pretend output.
+    pub fn maximize(&self, id: ToplevelId) {
+        let states = [XDG_TOPLEVEL_STATE_MAXIMIZED, XDG_TOPLEVEL_STATE_ACTIVATED];
+        self.configure(id, OUTPUT_SIZE.0, OUTPUT_SIZE.1, &states);
+    }
+
+    /// Configures the toplevel as fullscreen on the pretend output.
+    pub fn fullscreen(&self, id: ToplevelId) {
+        let states = [XDG_TOPLEVEL_STATE_FULLSCREEN, XDG_TOPLEVEL_STATE_ACTIVATED];
+        self.configure(id, OUTPUT_SIZE.0, OUTPUT_SIZE.1, &states);
+    }
+
+    /// Configures the toplevel as floating, leaving the size up to the client.
+    pub fn restore(&self, id: ToplevelId) {
+        self.configure(id, 0, 0, &[XDG_TOPLEVEL_STATE_ACTIVATED]);
+    }
+
+    /// Sends `xdg_toplevel.close`.
+    pub fn close(&self, id: ToplevelId) {
+        self.send(Command::Close(id));
+    }
+
+    fn send(&self, command: Command) {
+        self.commands.send(command).unwrap();
+        (&self.wake).write_all(&[0]).unwrap();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Toplevel>> {
+        self.shared.lock().unwrap()
+    }
+}
+
+impl Drop for HeadlessCompositor {
+    fn drop(&mut self) {
+        // The compositor thread is gone if it panicked, and a panicking drop would abort.
+        let _ = self.commands.send(Command::Quit);
+        let _ = (&self.wake).write_all(&[0]);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The compositor thread's state, passed to every callback.
+struct Server {
+    display: *mut wl_display,
+    shared: Arc<Mutex<Vec<Toplevel>>>,
+    commands: Receiver<Command>,
+    wake: UnixStream,
+    objects: RefCell<HashMap<*mut wl_resource, Object>>,
+    toplevels: RefCell<HashMap<ToplevelId, *mut wl_resource>>,
+    next_toplevel: Cell<u64>,
+}
+
+/// The user data of a `wl_global`.
+struct Global {
+    server: *const Server,
+    interface: *const wl_interface,
+}
+
+enum Object {
+    Surface {
+        role: *mut wl_resource,
+        buffer: *mut wl_resource,
+        frame_callbacks: Vec<*mut wl_resource>,
+    },
+    XdgSurface {
+        toplevel: *mut wl_resource,
+        geometry: Option<(i32, i32, i32, i32)>,
+        configured: bool,
+    },
+    Toplevel {
+        id: ToplevelId,
+        xdg_surface: *mut wl_resource,
+    },
+    Other,
+}
+
+unsafe fn run(
+    shared: Arc<Mutex<Vec<Toplevel>>>,
+    commands: Receiver<Command>,
+    wake: UnixStream,
+    ready: Sender<io::Result<()>>,
+) {
+    let Some(display) = NonNull::new(unsafe { wl_display_create() }) else {
+        let _ = ready.send(Err(io::Error::other("wl_display_create failed")));
+        return;
+    };
+    let display = display.as_ptr();
+
+    let server = Box::new(Server {
+        display,
+        shared,
+        commands,
+        wake,
+        objects: RefCell::new(HashMap::new()),
+        toplevels: RefCell::new(HashMap::new()),
+        next_toplevel: Cell::new(0),
+    });
+    let data = (&raw const *server).cast_mut().cast::<c_void>();
+
+    unsafe { wl_display_init_shm(display) };
+    // These outlive the display, which is destroyed before returning.
+    let globals = [
+        &raw const wl_compositor_interface,
+        &raw const wl_subcompositor_interface,
+        &raw const xdg_wm_base_interface,
+    ]
+    .map(|interface| Global {
+        server: &*server,
+        interface,
+    });
+    for global in &globals {
+        let global_data = (&raw const *global).cast_mut().cast::<c_void>();
+        let version = unsafe { (*global.interface).version };
+        unsafe { wl_global_create(display, global.interface, version, global_data, Some(bind)) };
+    }
+
+    unsafe {
+        let event_loop = wl_display_get_event_loop(display);
+        let wake_fd = server.wake.as_raw_fd();
+        wl_event_loop_add_fd(event_loop, wake_fd, WL_EVENT_READABLE, Some(on_wake), data);
+    }
+
+    let _ = ready.send(Ok(()));
+    unsafe { wl_display_run(display) };
+
+    unsafe {
+        wl_display_destroy_clients(display);
+        wl_display_destroy(display);
+    }
+}
+
+unsafe extern "C" fn on_wake(_fd: c_int, _mask: u32, data: *mut c_void) -> c_int {
+    let server = unsafe { &*data.cast::<Server>() };
+    let mut buf = [0; 64];
+    while let Ok(1..) = (&server.wake).read(&mut buf) {}
+
+    while let Ok(command) = server.commands.try_recv() {
+        match command {
+            Command::Connect(fd) => unsafe {
+                wl_client_create(server.display, fd.into_raw_fd());
+            },
+            Command::Configure(id, configure) => {
+                let toplevel = server.toplevels.borrow().get(&id).copied();
+                if let Some(toplevel) = toplevel {
+                    unsafe { server.send_configure(toplevel, configure) };
+                }
+            }
+            Command::Close(id) => {
+                let toplevel = server.toplevels.borrow().get(&id).copied();
+                if let Some(toplevel) = toplevel {
+                    unsafe { xdg_toplevel_send_close(toplevel) };
+                }
+            }
+            Command::Quit => unsafe { wl_display_terminate(server.display) },
+        }
+    }
+    0
+}
+
+unsafe extern "C" fn bind(client: *mut wl_client, data: *mut c_void, version: u32, id: u32) {
+    let global = unsafe { &*data.cast::<Global>() };
+    let server = unsafe { &*global.server };
+    create_resource(server, client, global.interface, version as c_int, id);
+}
+
+impl Server {
+    fn update(&self, id: ToplevelId, f: impl FnOnce(&mut Toplevel)) {
+        let mut shared = self.shared.lock().unwrap();
+        if let Some(toplevel) = shared.iter_mut().find(|toplevel| toplevel.id == id) {
+            f(toplevel);
+        }
+    }
+
+    unsafe fn send_configure(&self, toplevel: *mut wl_resource, configure: Configure) {
+        let (id, xdg_surface) = match self.objects.borrow().get(&toplevel) {
+            Some(&Object::Toplevel { id, xdg_surface }) => (id, xdg_surface),
+            _ => return,
+        };
+
+        let mut states = configure.states.clone();
+        let mut array = wl_array {
+            size: size_of_val(&states[..]),
+            alloc: size_of_val(&states[..]),
+            data: states.as_mut_ptr().cast(),
+        };
+        let serial = unsafe { wl_display_next_serial(self.display) };
+        unsafe {
+            xdg_toplevel_send_configure(toplevel, configure.width, configure.height, &mut array);
+            xdg_surface_send_configure(xdg_surface, serial);
+        }
+
+        if let Some(Object::XdgSurface { configured, .. }) =
+            self.objects.borrow_mut().get_mut(&xdg_surface)
+        {
+            *configured = true;
+        }
+        self.update(id, |toplevel| {
+            toplevel.configured = Some(configure);
+            toplevel.acked = false;
+        });
+    }
+}
+
+unsafe fn server<'a>(resource: *mut wl_resource) -> &'a Server {
+    unsafe { &*wl_resource_get_user_data(resource).cast::<Server>() }
+}
+
+unsafe fn string(arg: &wl_argument) -> Option<CString> {
+    let s = unsafe { arg.s };
+    (!s.is_null()).then(|| unsafe { CStr::from_ptr(s) }.into())
+}
+
+fn create_resource(
+    server: &Server,
+    client: *mut wl_client,
+    interface: *const wl_interface,
+    version: c_int,
+    id: u32,
+) -> *mut wl_resource {
+    let resource = unsafe { wl_resource_create(client, interface, version, id) };
+    if resource.is_null() {
+        unsafe { wl_client_post_no_memory(client) };
+        return resource;
+    }
+    let data = (&raw const *server).cast_mut().cast::<c_void>();
+    unsafe { wl_resource_set_dispatcher(resource, Some(dispatch), data, data, Some(on_destroy)) };
+    server.objects.borrow_mut().insert(resource, Object::Other);
+    resource
+}
+
+unsafe extern "C" fn dispatch(
+    _implementation: *const c_void,
+    target: *mut c_void,
+    _opcode: u32,
+    message: *const wl_message,
+    args: *mut wl_argument,
+) -> c_int {
+    let resource = target.cast::<wl_resource>();
+    let server = unsafe { server(resource) };
+    let client = unsafe { wl_resource_get_client(resource) };
+    let version = unsafe { wl_resource_get_version(resource) };
+    let message = unsafe { &*message };
+
+    // Create every new object generically, using the interface from the message's signature.
+    let signature = unsafe { CStr::from_ptr(message.signature) }.to_bytes();
+    let arg_count = signature.iter().filter(|c| c.is_ascii_lowercase()).count();
+    let args = unsafe { core::slice::from_raw_parts_mut(args, arg_count) };
+    let mut new = null_mut();
+    for (index, _) in signature
+        .iter()
+        .filter(|c| c.is_ascii_lowercase())
+        .enumerate()
+        .filter(|&(_, &c)| c == b'n')
+    {
+        let interface = unsafe { *message.types.add(index) };
+        new = create_resource(server, client, interface, version, unsafe { args[index].n });
+    }
+
+    let class = unsafe { CStr::from_ptr(wl_resource_get_class(resource)) }.to_bytes();
+    let name = unsafe { CStr::from_ptr(message.name) }.to_bytes();
+    let object = |arg: &wl_argument| unsafe { arg.o }.cast::<wl_resource>();
+
+    match (class, name) {
+        (b"wl_compositor", b"create_surface") => {
+            server.objects.borrow_mut().insert(
+                new,
+                Object::Surface {
+                    role: null_mut(),
+                    buffer: null_mut(),
+                    frame_callbacks: Vec::new(),
+                },
+            );
+        }
+        (b"wl_surface", b"attach") => {
+            if let Some(Object::Surface { buffer, .. }) =
+                server.objects.borrow_mut().get_mut(&resource)
+            {
+                *buffer = object(&args[0]);
+            }
+        }
+        (b"wl_surface", b"frame") => {
+            if let Some(Object::Surface {
+                frame_callbacks, ..
+            }) = server.objects.borrow_mut().get_mut(&resource)
+            {
+                frame_callbacks.push(new);
+            }
+        }
+        (b"wl_surface", b"commit") => unsafe { commit(server, resource) },
+        (b"xdg_wm_base", b"get_xdg_surface") => {
+            let surface = object(&args[1]);
+            let mut objects = server.objects.borrow_mut();
+            if let Some(Object::Surface { role, .. }) = objects.get_mut(&surface) {
+                *role = new;
+            }
+            objects.insert(
+                new,
+                Object::XdgSurface {
+                    toplevel: null_mut(),
+                    geometry: None,
+                    configured: false,
+                },
+            );
+        }
+        (b"xdg_surface", b"get_toplevel") => {
+            let id = ToplevelId(server.next_toplevel.get() + 1);
+            server.next_toplevel.set(id.0);
+            let mut objects = server.objects.borrow_mut();
+            if let Some(Object::XdgSurface { toplevel, .. }) = objects.get_mut(&resource) {
+                *toplevel = new;
+            }
+            objects.insert(
+                new,
+                Object::Toplevel {
+                    id,
+                    xdg_surface: resource,
+                },
+            );
+            server.toplevels.borrow_mut().insert(id, new);
+            server.shared.lock().unwrap().push(Toplevel {
+                id,
+                title: None,
+                app_id: None,
+                min_size: (0, 0),
+                max_size: (0, 0),
+                geometry: None,
+                configured: None,
+                acked: false,
+                requests: Vec::new(),
+            });
+        }
+        (b"xdg_surface", b"set_window_geometry") => {
+            let rect = unsafe { (args[0].i, args[1].i, args[2].i, args[3].i) };
+            if let Some(Object::XdgSurface { geometry, .. }) =
+                server.objects.borrow_mut().get_mut(&resource)
+            {
+                *geometry = Some(rect);
+            }
+        }
+        (b"xdg_surface", b"ack_configure") => {
+            let toplevel = match server.objects.borrow().get(&resource) {
+                Some(&Object::XdgSurface { toplevel, .. }) => toplevel,
+                _ => null_mut(),
+            };
+            if let Some(id) = toplevel_id(server, toplevel) {
+                server.update(id, |toplevel| toplevel.acked = true);
+            }
+        }
+        (b"xdg_toplevel", request) => {
+            if let Some(id) = toplevel_id(server, resource) {
+                unsafe { toplevel_request(server, resource, id, request, args) };
+            }
+        }
+        _ => {}
+    }
+
+    if name == b"destroy" {
+        unsafe { wl_resource_destroy(resource) };
+    }
+    0
+}
+
+fn toplevel_id(server: &Server, toplevel: *mut wl_resource) -> Option<ToplevelId> {
+    match server.objects.borrow().get(&toplevel) {
+        Some(&Object::Toplevel { id, .. }) => Some(id),
+        _ => None,
+    }
+}
+
+unsafe fn toplevel_request(
+    server: &Server,
+    resource: *mut wl_resource,
+    id: ToplevelId,
+    request: &[u8],
+    args: &[wl_argument],
+) {
+    let request = match request {
+        b"set_title" => {
+            let title = unsafe { string(&args[0]) };
+            return server.update(id, |toplevel| toplevel.title = title);
+        }
+        b"set_app_id" => {
+            let app_id = unsafe { string(&args[0]) };
+            return server.update(id, |toplevel| toplevel.app_id = app_id);
+        }
+        b"set_min_size" => {
+            let size = unsafe { (args[0].i, args[1].i) };
+            return server.update(id, |toplevel| toplevel.min_size = size);
+        }
+        b"set_max_size" => {
+            let size = unsafe { (args[0].i, args[1].i) };
+            return server.update(id, |toplevel| toplevel.max_size = size);
+        }
+        b"move" => ToplevelRequest::Move {
+            serial: unsafe { args[1].u },
+        },
+        b"resize" => ToplevelRequest::Resize {
+            serial: unsafe { args[1].u },
+            edges: unsafe { args[2].u },
+        },
+        b"set_fullscreen" => ToplevelRequest::SetFullscreen,
+        b"set_maximized" => ToplevelRequest::SetMaximized,
+        b"set_minimized" => ToplevelRequest::SetMinimized,
+        b"set_parent" => ToplevelRequest::SetParent,
+        b"show_window_menu" => ToplevelRequest::ShowWindowMenu {
+            serial: unsafe { args[1].u },
+            x: unsafe { args[2].i },
+            y: unsafe { args[3].i },
+        },
+        b"unset_fullscreen" => ToplevelRequest::UnsetFullscreen,
+        b"unset_maximized" => ToplevelRequest::UnsetMaximized,
+        _ => return,
+    };
+
+    // Grant state changes the way a real compositor would.
+    let configure = |width, height, state| Configure {
+        width,
+        height,
+        states: [state, XDG_TOPLEVEL_STATE_ACTIVATED][..].into(),
+    };
+    let response = match request {
+        ToplevelRequest::SetMaximized => Some(configure(
+            OUTPUT_SIZE.0,
+            OUTPUT_SIZE.1,
+            XDG_TOPLEVEL_STATE_MAXIMIZED,
+        )),
+        ToplevelRequest::SetFullscreen => Some(configure(
+            OUTPUT_SIZE.0,
+            OUTPUT_SIZE.1,
+            XDG_TOPLEVEL_STATE_FULLSCREEN,
+        )),
+        ToplevelRequest::UnsetMaximized | ToplevelRequest::UnsetFullscreen => Some(Configure {
+            width: 0,
+            height: 0,
+            states: [XDG_TOPLEVEL_STATE_ACTIVATED][..].into(),
+        }),
+        _ => None,
+    };
+
+    server.update(id, |toplevel| toplevel.requests.push(request));
+    if let Some(response) = response {
+        unsafe { server.send_configure(resource, response) };
+    }
+}
+
+unsafe fn commit(server: &Server, surface: *mut wl_resource) {
+    let (role, buffer, frame_callbacks) = match server.objects.borrow_mut().get_mut(&surface) {
+        Some(Object::Surface {
+            role,
+            buffer,
+            frame_callbacks,
+        }) => (
+            *role,
+            core::mem::replace(buffer, null_mut()),
+            core::mem::take(frame_callbacks),
+        ),
+        _ => return,
+    };
+
+    // Nothing is drawn, so buffers can be released and frame callbacks fired right away.
+    if !buffer.is_null() {
+        unsafe { wl_buffer_send_release(buffer) };
+    }
+    for callback in frame_callbacks {
+        unsafe {
+            wl_callback_send_done(callback, 0);
+            wl_resource_destroy(callback);
+        }
+    }
+
+    let (toplevel, geometry, configured) = match server.objects.borrow().get(&role) {
+        Some(&Object::XdgSurface {
+            toplevel,
+            geometry,
+            configured,
+            ..
+        }) => (toplevel, geometry, configured),
+        _ => return,
+    };
+    let Some(id) = toplevel_id(server, toplevel) else {
+        return;
+    };
+    server.update(id, |toplevel| toplevel.geometry = geometry);
+
+    // The initial commit of a toplevel asks for its first configure.
+    if !configured {
+        let configure = Configure {
+            width: 0,
+            height: 0,
+            states: [XDG_TOPLEVEL_STATE_ACTIVATED][..].into(),
+        };
+        unsafe { server.send_configure(toplevel, configure) };
+    }
+}
+
+unsafe extern "C" fn on_destroy(resource: *mut wl_resource) {
+    let server = unsafe { server(resource) };
+    let object = server.objects.borrow_mut().remove(&resource);
+    if let Some(Object::Toplevel { id, .. }) = object {
+        server.toplevels.borrow_mut().remove(&id);
+        server
+            .shared
+            .lock()
+            .unwrap()
+            .retain(|toplevel| toplevel.id != id);
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    cell::Cell,
+    ffi::{CStr, c_char, c_void},
+    os::fd::IntoRawFd,
+    ptr,
+    time::{Duration, Instant},
+};
+
+use wayland_headers::wayland_client::*;
+
+use super::HeadlessCompositor;
+use crate::libdecor::*;
+
+#[link(name = "wayland-client")]
+unsafe extern "C" {}
+
+#[link(name = "decor-0")]
+unsafe extern "C" {}
+
+#[derive(Default)]
+struct Window {
+    wl_compositor: Cell<*mut wl_compositor>,
+    content_size: Cell<(i32, i32)>,
+    window_state: Cell<libdecor_window_state>,
+    closed: Cell<bool>,
+}
+
+impl Window {
+    fn from_user_data<'a>(user_data: *mut c_void) -> &'a Window {
+        unsafe { &*user_data.cast::<Window>() }
+    }
+}
+
+#[test]
+fn headless() {
+    let compositor = HeadlessCompositor::new().unwrap();
+    let fd = compositor.connect().unwrap();
+    let wl_display = unsafe { wl_display_connect_to_fd(fd.into_raw_fd()) };
+    assert!(!wl_display.is_null());
+
+    let window = Window::default();
+    let window = &window;
+    let window_user_data = ptr::from_ref(window).cast_mut().cast::<c_void>();
+
+    let wl_registry = unsafe { wl_display_get_registry(wl_display) };
+    unsafe { wl_registry_add_listener(wl_registry, &REGISTRY_LISTENER, window_user_data) };
+    unsafe { wl_display_roundtrip(wl_display) };
+    assert!(!window.wl_compositor.get().is_null());
+    let wl_surface = unsafe { wl_compositor_create_surface(window.wl_compositor.get()) };
+
+    let mut iface = libdecor_interface {
+        error: Some(on_libdecor_error),
+        ..unsafe { core::mem::zeroed() }
+    };
+    let mut frame_iface = libdecor_frame_interface {
+        configure: Some(on_libdecor_frame_configure),
+        close: Some(on_libdecor_frame_close),
+        ..unsafe { core::mem::zeroed() }
+    };
+    let libdecor = unsafe { libdecor_new(wl_display, &mut iface) };
+    assert!(!libdecor.is_null());
+    let frame =
+        unsafe { libdecor_decorate(libdecor, wl_surface, &mut frame_iface, window_user_data) };
+    assert!(!frame.is_null());
+    unsafe {
+        libdecor_frame_set_title(frame, c"headless".as_ptr());
+        libdecor_frame_map(frame);
+    }
+
+    dispatch_until(libdecor, || {
+        let toplevels = compositor.toplevels();
+        toplevels.first().is_some_and(|toplevel| toplevel.acked)
+    });
+    let toplevel = compositor.toplevels()[0].clone();
+    assert_eq!(toplevel.title.as_deref(), Some(c"headless"));
+    assert_eq!(window.content_size.get(), (640, 480));
+
+    compositor.maximize(toplevel.id);
+    dispatch_until(libdecor, || {
+        window.window_state.get() & LIBDECOR_WINDOW_STATE_MAXIMIZED != 0
+    });
+
+    compositor.fullscreen(toplevel.id);
+    dispatch_until(libdecor, || {
+        window.window_state.get() & LIBDECOR_WINDOW_STATE_FULLSCREEN != 0
+    });
+    assert_eq!(window.content_size.get(), super::OUTPUT_SIZE);
+
+    compositor.close(toplevel.id);
+    dispatch_until(libdecor, || window.closed.get());
+
+    unsafe {
+        libdecor_frame_unref(frame);
+        libdecor_unref(libdecor);
+        wl_surface_destroy(wl_surface);
+        wl_display_disconnect(wl_display);
+    }
+}
+
+/// Dispatches until `done` returns `true`, failing the test after a few seconds.
+fn dispatch_until(libdecor: *mut libdecor, mut done: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !done() {
+        assert!(
+            Instant::now() < deadline,
+            "timed out waiting for the compositor"
+        );
+        assert!(unsafe { libdecor_dispatch(libdecor, 100) } >= 0);
+    }
+}
+
+static REGISTRY_LISTENER: wl_registry_listener = wl_registry_listener {
+    global: Some(on_registry_global),
+    global_remove: Some(on_registry_global_remove),
+};
+
+unsafe extern "C" fn on_registry_global(
+    window_user_data: *mut c_void,
+    wl_registry: *mut wl_registry,
+    global_id: u32,
+    interface_name: *const c_char,
+    _interface_version: u32,
+) {
+    let window = Window::from_user_data(window_user_data);
+    if unsafe { CStr::from_ptr(interface_name) } == c"wl_compositor" {
+        let wl_compositor =
+            unsafe { wl_registry_bind(wl_registry, global_id, &wl_compositor_interface, 4) };
+        window.wl_compositor.set(wl_compositor.cast());
+    }
+}
+
+unsafe extern "C" fn on_registry_global_remove(
+    _window_user_data: *mut c_void,
+    _wl_registry: *mut wl_registry,
+    _global_id: u32,
+) {
+}
+
+unsafe extern "C" fn on_libdecor_error(
+    _libdecor: *mut libdecor,
+    _error: libdecor_error,
+    message: *const c_char,
+) {
+    panic!("{:?}", unsafe { CStr::from_ptr(message) });
+}
+
+unsafe extern "C" fn on_libdecor_frame_configure(
+    frame: *mut libdecor_frame,
+    configuration: *mut libdecor_configuration,
+    window_user_data: *mut c_void,
+) {
+    let window = Window::from_user_data(window_user_data);
+
+    let (mut width, mut height) = (640, 480);
+    unsafe {
+        libdecor_configuration_get_content_size(configuration, frame, &mut width, &mut height)
+    };
+    let mut window_state = LIBDECOR_WINDOW_STATE_NONE;
+    unsafe { libdecor_configuration_get_window_state(configuration, &mut window_state) };
+
+    let state = unsafe { libdecor_state_new(width, height) };
+    unsafe {
+        libdecor_frame_commit(frame, state, configuration);
+        libdecor_state_free(state);
+    }
+    window.content_size.set((width, height));
+    window.window_state.set(window_state);
+}
+
+unsafe extern "C" fn on_libdecor_frame_close(
+    _frame: *mut libdecor_frame,
+    window_user_data: *mut c_void,
+) {
+    Window::from_user_data(window_user_data).closed.set(true);
+}
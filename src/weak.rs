@@ -0,0 +1,95 @@
+//! Functions newer than libdecor 0.1.0, resolved with `dlsym(RTLD_DEFAULT, ...)` on first use.
+//!
+//! Unlike the declarations in [`crate::libdecor`], these don't prevent a binary from loading when
+//! the installed libdecor predates them.
+//!
+//! `dlsym(RTLD_DEFAULT, ...)` only searches dynamic symbol tables, so these also return `None`
+//! when libdecor is linked statically into a binary that isn't linked with `-rdynamic`, however
+//! new that libdecor is.
+
+use core::{
+    ffi::{c_char, c_int, c_void},
+    mem::transmute,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use libc::{RTLD_DEFAULT, dlsym};
+
+use crate::libdecor::*;
+
+#[cfg(test)]
+mod test;
+
+#[cfg(test)]
+std::thread_local! {
+    /// Makes every symbol resolve to null on this thread, as if libdecor predated them.
+    static HIDDEN: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+
+/// A lazily resolved symbol address, null if the symbol doesn't exist.
+struct Symbol(AtomicPtr<c_void>);
+
+impl Symbol {
+    const UNRESOLVED: *mut c_void = ptr::dangling_mut();
+
+    const fn new() -> Self {
+        Self(AtomicPtr::new(Self::UNRESOLVED))
+    }
+
+    unsafe fn resolve(&self, name: *const c_char) -> *mut c_void {
+        #[cfg(test)]
+        if HIDDEN.get() {
+            return ptr::null_mut();
+        }
+        let mut address = self.0.load(Ordering::Relaxed);
+        if address == Self::UNRESOLVED {
+            address = unsafe { dlsym(RTLD_DEFAULT, name) };
+            self.0.store(address, Ordering::Relaxed);
+        }
+        address
+    }
+}
+
+macro_rules! weak_functions {
+    ($(pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        $(
+            pub fn $name() -> Option<unsafe extern "C" fn($($arg: $ty),*) $(-> $ret)?> {
+                static SYMBOL: Symbol = Symbol::new();
+                let name = concat!(stringify!($name), "\0").as_ptr().cast();
+                let address = unsafe { SYMBOL.resolve(name) };
+                match address.is_null() {
+                    true => None,
+                    false => Some(unsafe {
+                        transmute::<*mut c_void, unsafe extern "C" fn($($ty),*) $(-> $ret)?>(
+                            address,
+                        )
+                    }),
+                }
+            }
+        )*
+    };
+}
+
+weak_functions! {
+    pub fn libdecor_frame_get_max_content_size(
+        frame: *const libdecor_frame,
+        content_width: *mut c_int,
+        content_height: *mut c_int,
+    );
+
+    pub fn libdecor_frame_get_min_content_size(
+        frame: *const libdecor_frame,
+        content_width: *mut c_int,
+        content_height: *mut c_int,
+    );
+}
+
+/// Runs `f` with every symbol resolving to null on this thread.
+#[cfg(test)]
+pub(crate) fn hidden<R>(f: impl FnOnce() -> R) -> R {
+    HIDDEN.set(true);
+    let result = f();
+    HIDDEN.set(false);
+    result
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+// The mock defines the functions newer than libdecor 0.1.0 along with them.
+#[cfg(all(feature = "mock", feature = "libdecor-0-1-1"))]
+#[test]
+fn resolves_exported_symbols() {
+    assert!(libdecor_frame_get_min_content_size().is_some());
+    assert!(libdecor_frame_get_max_content_size().is_some());
+}
+
+#[test]
+fn hidden_symbols_resolve_to_none() {
+    hidden(|| {
+        assert!(libdecor_frame_get_min_content_size().is_none());
+        assert!(libdecor_frame_get_max_content_size().is_none());
+    });
+}
+
+#[test]
+fn missing_symbols_resolve_to_null() {
+    static SYMBOL: Symbol = Symbol::new();
+    let name = c"libdecor_headers_missing".as_ptr();
+    assert!(unsafe { SYMBOL.resolve(name) }.is_null());
+    // The failed lookup is cached rather than repeated.
+    assert!(SYMBOL.0.load(Ordering::Relaxed).is_null());
+}